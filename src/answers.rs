@@ -0,0 +1,224 @@
+use crate::days;
+use crate::runner::{self, InputSource};
+use crate::shared::Outcome;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Default location of the expected answers manifest
+pub const DEFAULT_ANSWERS_FILE: &str = "./inputs/answers.toml";
+
+/// Expected answers for the real inputs, keyed by day and part
+///
+/// The manifest is a TOML file like:
+///
+/// ```toml
+/// [day_01]
+/// part_1 = 1234
+/// part_2 = "some text"
+/// ```
+///
+/// Numbers too big for a TOML integer can be written as a string instead, and multi-line strings
+/// are compared against grid answers.
+#[derive(Debug, Default)]
+pub struct Answers {
+    expected: HashMap<(u8, u8), Outcome>,
+}
+
+impl Answers {
+    /// Load the manifest from a file, a missing file simply gives no answers
+    ///
+    /// # Errors
+    ///
+    /// If the file cannot be read or parsed.
+    pub fn from_file(path: &Path) -> Result<Self, String> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(path)
+            .map_err(|err| format!("Failed to read {}: {err}", path.display()))?;
+        Self::parse(&content).map_err(|err| format!("In {}: {err}", path.display()))
+    }
+
+    /// Parse the content of a manifest
+    ///
+    /// # Errors
+    ///
+    /// If the content is not valid TOML or does not follow the manifest layout.
+    pub fn parse(content: &str) -> Result<Self, String> {
+        let table: toml::Table = content.parse().map_err(|err| format!("{err}"))?;
+        let mut expected = HashMap::new();
+
+        for (day_key, parts) in &table {
+            let day = number_from_key(day_key, "day_")?;
+            let parts = parts
+                .as_table()
+                .ok_or_else(|| format!("Expected a table for `{day_key}`"))?;
+
+            for (part_key, value) in parts {
+                let part = number_from_key(part_key, "part_")?;
+                let outcome = outcome_from_value(value)
+                    .ok_or_else(|| format!("Unsupported value for `{day_key}.{part_key}`"))?;
+                expected.insert((day, part), outcome);
+            }
+        }
+
+        Ok(Self { expected })
+    }
+
+    /// Get the expected answer for a day and part, if known
+    #[must_use]
+    pub fn get(&self, day: u8, part: u8) -> Option<&Outcome> {
+        self.expected.get(&(day, part))
+    }
+}
+
+/// Turn a key like `day_05` into `5`
+pub(crate) fn number_from_key(key: &str, prefix: &str) -> Result<u8, String> {
+    key.strip_prefix(prefix)
+        .and_then(|num| num.parse().ok())
+        .ok_or_else(|| format!("Invalid key `{key}`, expected like `{prefix}01`"))
+}
+
+/// Turn a TOML integer or string into an answer, `None` for other types
+pub(crate) fn outcome_from_value(value: &toml::Value) -> Option<Outcome> {
+    match value {
+        toml::Value::Integer(n) => Some(match u64::try_from(*n) {
+            Ok(n) => Outcome::U64(n),
+            Err(_) => Outcome::I64(*n),
+        }),
+        toml::Value::String(txt) => Some(Outcome::from_text(txt)),
+        _ => None,
+    }
+}
+
+/// Result of checking one answer against the manifest
+#[derive(Debug, PartialEq)]
+pub enum Status {
+    Pass,
+    Fail { expected: Outcome, actual: Outcome },
+    Missing,       // No expected answer in the manifest
+    Error(String), // Solver failed to produce an answer
+}
+
+/// Verification of a single day and part
+#[derive(Debug)]
+pub struct Verification {
+    pub day: u8,
+    pub part: u8,
+    pub status: Status,
+}
+
+/// Run every day that has an expected answer and compare the outcomes
+///
+/// `inputs` gives the input to use for each day.
+pub fn verify(
+    answers: &Answers,
+    parts: &[u8],
+    inputs: impl Fn(u8) -> InputSource,
+) -> Vec<Verification> {
+    let mut verifications = Vec::new();
+
+    for day in days::day_numbers() {
+        for &part in parts {
+            let status = match answers.get(day, part) {
+                None => Status::Missing,
+                Some(expected) => match runner::run_part(day, part, &inputs(day)).result {
+                    Ok(outcome) if outcome == *expected => Status::Pass,
+                    Ok(actual) => Status::Fail {
+                        expected: expected.clone(),
+                        actual,
+                    },
+                    Err(msg) => Status::Error(msg),
+                },
+            };
+            verifications.push(Verification { day, part, status });
+        }
+    }
+
+    verifications
+}
+
+/// Print a list of verifications as a table
+pub fn print_verifications(verifications: &[Verification]) {
+    println!("{:>3} | {:>4} | {:<7} | Details", "Day", "Part", "Status");
+    println!("{:-<4}+{:-<6}+{:-<9}+{:-<20}", "", "", "", "");
+    for verification in verifications {
+        let (day, part) = (verification.day, verification.part);
+        let (status, details) = match &verification.status {
+            Status::Pass => ("pass", String::new()),
+            Status::Fail { expected, actual } => {
+                ("FAIL", format!("got {actual}, expected {expected}"))
+            }
+            Status::Missing => ("missing", String::new()),
+            Status::Error(msg) => ("ERROR", msg.clone()),
+        };
+        println!("{day:>3} | {part:>4} | {status:<7} | {details}");
+    }
+}
+
+/// Resolve the manifest path, where `"default"` means the usual location
+#[must_use]
+pub fn answers_file(path: PathBuf) -> PathBuf {
+    if path.to_str() == Some("default") {
+        PathBuf::from(DEFAULT_ANSWERS_FILE)
+    } else {
+        path
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_answers() {
+        let answers = Answers::parse(
+            "[day_01]\npart_1 = 3\npart_2 = \"18446744073709551615\"\n\n[day_11]\npart_1 = \"d11p1\"\n",
+        )
+        .unwrap();
+        assert_eq!(answers.get(1, 1), Some(&Outcome::U64(3)));
+        assert_eq!(answers.get(1, 2), Some(&Outcome::U64(u64::MAX)));
+        assert_eq!(
+            answers.get(11, 1),
+            Some(&Outcome::Text(String::from("d11p1")))
+        );
+        assert_eq!(answers.get(11, 2), None);
+
+        let answers =
+            Answers::parse("[day_02]\npart_1 = -3\npart_2 = \"\"\"\n#.\n.#\n\"\"\"\n").unwrap();
+        assert_eq!(answers.get(2, 1), Some(&Outcome::I64(-3)));
+        assert_eq!(answers.get(2, 2).unwrap().kind(), "Grid");
+    }
+
+    #[test]
+    fn parse_answers_invalid() {
+        assert!(Answers::parse("[day_x]\npart_1 = 3\n").is_err());
+        assert!(Answers::parse("[day_01]\nsecond = 3\n").is_err());
+        assert!(Answers::parse("[day_01]\npart_1 = 1.5\n").is_err());
+    }
+
+    #[test]
+    fn verify_samples() {
+        let answers = Answers::parse("[day_01]\npart_1 = 3\npart_2 = 7\n").unwrap();
+        let verifications = verify(&answers, &[1, 2], |day| {
+            InputSource::File(PathBuf::from(format!("tests/day_{day:02}/sample.txt")))
+        });
+        let status_of = |day, part| {
+            &verifications
+                .iter()
+                .find(|v| v.day == day && v.part == part)
+                .unwrap()
+                .status
+        };
+        assert_eq!(status_of(1, 1), &Status::Pass);
+        assert_eq!(
+            status_of(1, 2),
+            &Status::Fail {
+                expected: Outcome::U64(7),
+                actual: Outcome::U64(6)
+            }
+        );
+        assert_eq!(status_of(2, 1), &Status::Missing);
+    }
+}
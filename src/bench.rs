@@ -0,0 +1,432 @@
+//! Repeated timing of the solvers, with statistics and comparison against a saved baseline
+
+use crate::answers::number_from_key;
+use crate::days::get_solver;
+use crate::memory::{self, AllocStats};
+use crate::runner::{self, InputSource};
+use crate::shared::{SolveError, Trace};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::panic;
+use std::path::Path;
+use std::time::Instant;
+
+/// Number of repetitions for a benchmark
+#[derive(Debug, Clone, Copy)]
+pub struct BenchConfig {
+    pub warmup: u32, // Runs that are not measured
+    pub runs: u32,
+    pub profile_alloc: bool, // Count allocations in one more run, see [`memory`]
+}
+
+/// Step of running a solver that is timed separately
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Phase {
+    Read,
+    Parse,
+    Solve,
+}
+
+impl Phase {
+    const ALL: [Phase; 3] = [Phase::Read, Phase::Parse, Phase::Solve];
+}
+
+impl Display for Phase {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Phase::Read => f.pad("read"),
+            Phase::Parse => f.pad("parse"),
+            Phase::Solve => f.pad("solve"),
+        }
+    }
+}
+
+/// Statistics over a set of timings, all in milliseconds
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stats {
+    pub min: f64,
+    pub median: f64,
+    pub mean: f64,
+    pub stddev: f64,
+}
+
+impl Stats {
+    /// Compute statistics from a non-empty list of samples
+    ///
+    /// # Panics
+    ///
+    /// If `samples` is empty.
+    #[must_use]
+    pub fn from_samples(samples: &[f64]) -> Self {
+        assert!(!samples.is_empty(), "Need at least one sample");
+        let mut sorted = samples.to_vec();
+        sorted.sort_by(f64::total_cmp);
+
+        let n = sorted.len();
+        let median = if n.is_multiple_of(2) {
+            f64::midpoint(sorted[n / 2 - 1], sorted[n / 2])
+        } else {
+            sorted[n / 2]
+        };
+        #[allow(clippy::cast_precision_loss)] // Sample counts are tiny
+        let count = n as f64;
+        let mean = sorted.iter().sum::<f64>() / count;
+        let variance = sorted.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / count;
+
+        Self {
+            min: sorted[0],
+            median,
+            mean,
+            stddev: variance.sqrt(),
+        }
+    }
+}
+
+/// Benchmark result for a single day and part
+#[derive(Debug)]
+pub struct Benchmark {
+    pub day: u8,
+    pub part: u8,
+    pub result: Result<HashMap<Phase, Stats>, String>, // Phases that were measured, or an error
+    pub allocations: HashMap<Phase, AllocStats>,       // Only when profiling allocations
+}
+
+/// Time the solver of a day for one part, repeatedly
+///
+/// Reading, parsing and solving are each timed separately. Allocations are counted in a separate
+/// run after the timed ones, such that counting does not affect the timings.
+#[must_use]
+pub fn bench_part(day: u8, part: u8, source: &InputSource, config: BenchConfig) -> Benchmark {
+    let solver = get_solver(day);
+    let mut allocations = HashMap::new();
+
+    let result = runner::without_panic_output(|| {
+        panic::catch_unwind(panic::AssertUnwindSafe(|| {
+            let mut samples: HashMap<Phase, Vec<f64>> = HashMap::new();
+            let trace = Trace::disabled();
+            let with_file = |err: SolveError| err.with_file(source.label()).to_string();
+
+            for run in 0..(config.warmup + config.runs) {
+                let time_start = Instant::now();
+                let input = source.read().map_err(|err| err.to_string())?;
+                let time_read = time_start.elapsed();
+
+                let time_start = Instant::now();
+                let parsed = solver.parse_input(&input, &trace).map_err(with_file)?;
+                let time_parse = time_start.elapsed();
+
+                let time_start = Instant::now();
+                solver
+                    .solve_part(&parsed, part, &trace)
+                    .map_err(with_file)?;
+                let time_solve = time_start.elapsed();
+
+                if run >= config.warmup {
+                    for (phase, time) in [
+                        (Phase::Read, time_read),
+                        (Phase::Parse, time_parse),
+                        (Phase::Solve, time_solve),
+                    ] {
+                        samples
+                            .entry(phase)
+                            .or_default()
+                            .push(time.as_secs_f64() * 1.0e3);
+                    }
+                }
+            }
+
+            if config.profile_alloc {
+                let (input, read) = memory::measure(|| source.read());
+                let input = input.map_err(|err| err.to_string())?;
+                let (parsed, parse) = memory::measure(|| solver.parse_input(&input, &trace));
+                let parsed = parsed.map_err(with_file)?;
+                let (_, solve) = memory::measure(|| solver.solve_part(&parsed, part, &trace));
+                allocations = HashMap::from([
+                    (Phase::Read, read),
+                    (Phase::Parse, parse),
+                    (Phase::Solve, solve),
+                ]);
+            }
+
+            Ok(samples
+                .into_iter()
+                .map(|(phase, list)| (phase, Stats::from_samples(&list)))
+                .collect())
+        }))
+    })
+    .unwrap_or_else(|payload| Err(format!("panicked: {}", runner::panic_message(&*payload))));
+
+    Benchmark {
+        day,
+        part,
+        result,
+        allocations,
+    }
+}
+
+/// Benchmark a list of days, continuing after failures
+#[must_use]
+pub fn bench_days(days: &[u8], parts: &[u8], config: BenchConfig) -> Vec<Benchmark> {
+    days.iter()
+        .flat_map(|&day| {
+            parts.iter().map(move |&part| {
+                let source = InputSource::File(runner::default_input_file(day));
+                bench_part(day, part, &source, config)
+            })
+        })
+        .collect()
+}
+
+/// Median timings of an earlier benchmark, to compare against
+#[derive(Debug, Default)]
+pub struct Baseline {
+    medians: HashMap<(u8, u8, Phase), f64>,
+}
+
+impl Baseline {
+    /// Collect the medians of a set of benchmarks
+    #[must_use]
+    pub fn from_benchmarks(benchmarks: &[Benchmark]) -> Self {
+        let mut medians = HashMap::new();
+        for benchmark in benchmarks {
+            if let Ok(phases) = &benchmark.result {
+                for (&phase, stats) in phases {
+                    medians.insert((benchmark.day, benchmark.part, phase), stats.median);
+                }
+            }
+        }
+        Self { medians }
+    }
+
+    /// Get the median time of an earlier run
+    #[must_use]
+    pub fn get(&self, day: u8, part: u8, phase: Phase) -> Option<f64> {
+        self.medians.get(&(day, part, phase)).copied()
+    }
+
+    /// Write the baseline to a TOML file
+    ///
+    /// # Errors
+    ///
+    /// If the file cannot be written.
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let mut nested: BTreeMap<String, BTreeMap<String, toml::Table>> = BTreeMap::new();
+        for (&(day, part, phase), &median) in &self.medians {
+            nested
+                .entry(format!("day_{day:02}"))
+                .or_default()
+                .entry(format!("part_{part}"))
+                .or_default()
+                .insert(phase.to_string(), toml::Value::Float(median));
+        }
+        let table: toml::Table = nested
+            .into_iter()
+            .map(|(day_key, parts)| {
+                let parts = parts
+                    .into_iter()
+                    .map(|(part_key, phases)| (part_key, toml::Value::Table(phases)))
+                    .collect();
+                (day_key, toml::Value::Table(parts))
+            })
+            .collect();
+        fs::write(path, table.to_string())
+            .map_err(|err| format!("Failed to write {}: {err}", path.display()))
+    }
+
+    /// Load a baseline from a TOML file
+    ///
+    /// # Errors
+    ///
+    /// If the file cannot be read or parsed.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let content = fs::read_to_string(path)
+            .map_err(|err| format!("Failed to read {}: {err}", path.display()))?;
+        Self::parse(&content).map_err(|err| format!("In {}: {err}", path.display()))
+    }
+
+    /// Parse the content of a baseline file
+    ///
+    /// # Errors
+    ///
+    /// If the content is not valid TOML or does not follow the baseline layout.
+    pub fn parse(content: &str) -> Result<Self, String> {
+        let table: toml::Table = content.parse().map_err(|err| format!("{err}"))?;
+        let mut medians = HashMap::new();
+
+        let as_table = |value: &toml::Value, key: &str| -> Result<toml::Table, String> {
+            value
+                .as_table()
+                .cloned()
+                .ok_or_else(|| format!("Expected a table for `{key}`"))
+        };
+
+        for (day_key, parts) in &table {
+            let day = number_from_key(day_key, "day_")?;
+            for (part_key, phases) in as_table(parts, day_key)? {
+                let part = number_from_key(&part_key, "part_")?;
+                for (phase_key, value) in as_table(&phases, &part_key)? {
+                    let phase = Phase::ALL
+                        .into_iter()
+                        .find(|phase| phase.to_string() == phase_key)
+                        .ok_or_else(|| format!("Unknown phase `{phase_key}`"))?;
+                    let median = value
+                        .as_float()
+                        .ok_or_else(|| format!("Expected a number for `{phase_key}`"))?;
+                    medians.insert((day, part, phase), median);
+                }
+            }
+        }
+
+        Ok(Self { medians })
+    }
+}
+
+/// Relative change of a median compared to a baseline, `None` if there is nothing to compare
+#[must_use]
+pub fn relative_change(
+    baseline: &Baseline,
+    day: u8,
+    part: u8,
+    phase: Phase,
+    median: f64,
+) -> Option<f64> {
+    baseline
+        .get(day, part, phase)
+        .filter(|&old| old > 0.0)
+        .map(|old| (median - old) / old)
+}
+
+/// Print benchmark statistics as a table, flagging slow-downs beyond `threshold` (fraction)
+///
+/// Returns `true` when any regression was found.
+#[must_use]
+pub fn print_benchmarks(benchmarks: &[Benchmark], baseline: &Baseline, threshold: f64) -> bool {
+    let mut regression = false;
+
+    println!(
+        "{:>3} | {:>4} | {:<5} | {:>10} | {:>10} | {:>10} | {:>10} | Baseline",
+        "Day", "Part", "Phase", "Min [ms]", "Median", "Mean", "Stddev"
+    );
+    println!(
+        "{:-<4}+{:-<6}+{:-<7}+{:-<12}+{:-<12}+{:-<12}+{:-<12}+{:-<20}",
+        "", "", "", "", "", "", "", ""
+    );
+    for benchmark in benchmarks {
+        let (day, part) = (benchmark.day, benchmark.part);
+        let phases = match &benchmark.result {
+            Ok(phases) => phases,
+            Err(msg) => {
+                println!("{day:>3} | {part:>4} | FAILED: {msg}");
+                continue;
+            }
+        };
+        for phase in Phase::ALL {
+            let Some(stats) = phases.get(&phase) else {
+                continue; // Phase not measured
+            };
+            let comparison = match relative_change(baseline, day, part, phase, stats.median) {
+                Some(change) if change > threshold => {
+                    regression = true;
+                    format!("{:+.1}% REGRESSION", change * 100.0)
+                }
+                Some(change) => format!("{:+.1}%", change * 100.0),
+                None => String::new(),
+            };
+            println!(
+                "{day:>3} | {part:>4} | {phase:<5} | {:>10.4} | {:>10.4} | {:>10.4} | {:>10.4} | {comparison}",
+                stats.min, stats.median, stats.mean, stats.stddev
+            );
+        }
+    }
+
+    regression
+}
+
+/// Print the counted allocations as a table, per day, part and phase
+pub fn print_allocations(benchmarks: &[Benchmark]) {
+    println!(
+        "{:>3} | {:>4} | {:<5} | {:>11} | {:>14} | {:>14}",
+        "Day", "Part", "Phase", "Allocations", "Bytes", "Peak [bytes]"
+    );
+    println!(
+        "{:-<4}+{:-<6}+{:-<7}+{:-<13}+{:-<16}+{:-<15}",
+        "", "", "", "", "", ""
+    );
+    for benchmark in benchmarks {
+        let (day, part) = (benchmark.day, benchmark.part);
+        for phase in Phase::ALL {
+            let Some(stats) = benchmark.allocations.get(&phase) else {
+                continue; // Not profiled, or failed before this phase
+            };
+            println!(
+                "{day:>3} | {part:>4} | {phase:<5} | {:>11} | {:>14} | {:>14}",
+                stats.allocations, stats.bytes, stats.peak
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[allow(clippy::float_cmp)] // Values are exact
+    #[test]
+    fn stats_from_samples() {
+        let stats = Stats::from_samples(&[4.0, 1.0, 3.0, 2.0]);
+        assert_eq!(stats.min, 1.0);
+        assert_eq!(stats.median, 2.5);
+        assert_eq!(stats.mean, 2.5);
+        assert!((stats.stddev - 1.25_f64.sqrt()).abs() < 1.0e-12);
+
+        let stats = Stats::from_samples(&[7.0]);
+        assert_eq!((stats.median, stats.stddev), (7.0, 0.0));
+    }
+
+    #[test]
+    fn bench_sample() {
+        let source = InputSource::File(PathBuf::from("tests/day_01/sample.txt"));
+        let config = BenchConfig {
+            warmup: 1,
+            runs: 3,
+            profile_alloc: false,
+        };
+        let benchmark = bench_part(1, 1, &source, config);
+        let phases = benchmark.result.unwrap();
+        assert!(phases.contains_key(&Phase::Read));
+        assert!(phases.contains_key(&Phase::Parse));
+        assert!(phases.contains_key(&Phase::Solve));
+        assert!(benchmark.allocations.is_empty());
+
+        let config = BenchConfig {
+            profile_alloc: true,
+            ..config
+        };
+        let benchmark = bench_part(1, 1, &source, config);
+        assert_eq!(benchmark.allocations.len(), 3);
+    }
+
+    #[test]
+    fn baseline_round_trip() {
+        let benchmarks = vec![Benchmark {
+            day: 3,
+            part: 2,
+            result: Ok(HashMap::from([(
+                Phase::Solve,
+                Stats::from_samples(&[1.0, 2.0, 6.0]),
+            )])),
+            allocations: HashMap::new(),
+        }];
+        let baseline = Baseline::from_benchmarks(&benchmarks);
+        let path = std::env::temp_dir().join("advent_of_code_2025_baseline_test.toml");
+        baseline.save(&path).unwrap();
+        let loaded = Baseline::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded.get(3, 2, Phase::Solve), Some(2.0));
+        assert_eq!(loaded.get(3, 2, Phase::Read), None);
+        assert_eq!(relative_change(&loaded, 3, 2, Phase::Solve, 3.0), Some(0.5));
+    }
+}
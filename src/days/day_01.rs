@@ -1,135 +1,255 @@
-use crate::shared::{Outcome, Solution};
-
-use std::io::BufRead;
-use std::path::PathBuf;
+use crate::days::DayInfo;
+use crate::shared::{Level, Outcome, ParamSpec, Params, Solution, SolveError, SolveResult, Trace};
 
 /// Solver for day 1
-pub struct Day01;
+pub struct Day01 {
+    pub size: i32,  // Number of positions on the dial
+    pub start: i32, // Position the dial starts at
+}
 
 impl Day01 {
     /// Convert a piece of string like `L8` to `-8`
-    fn step_to_number(line: &str) -> i32 {
+    fn step_to_number(line: &str) -> Result<i32, SolveError> {
         let mut chars = line.chars();
-        let dir = chars.next().expect("Line is empty");
-        let mut step: i32 = chars.as_str().parse().expect("Failed to parse to int");
-        if dir == 'L' {
-            step *= -1;
+        let sign = match chars.next() {
+            Some('L') => -1,
+            Some('R') => 1,
+            Some(c) => {
+                return Err(
+                    SolveError::parse(format!("Expected `L` or `R`, got `{c}`")).at_column(1)
+                );
+            }
+            None => return Err(SolveError::parse("Line is empty")),
+        };
+        let step: i32 = chars.as_str().parse().map_err(|_| {
+            SolveError::parse(format!("Expected a number, got `{}`", chars.as_str())).at_column(2)
+        })?;
+        Ok(sign * step)
+    }
+
+    /// Read all steps from the input
+    fn read_steps(input: &str) -> Result<Vec<i32>, SolveError> {
+        input
+            .lines()
+            .enumerate()
+            .map(|(idx, line)| Self::step_to_number(line).map_err(|err| err.on_line(idx + 1)))
+            .collect()
+    }
+
+    /// Make sure the start position is on the dial
+    fn check_start(&self) -> Result<(), SolveError> {
+        if self.start >= self.size {
+            return Err(SolveError::Unsolvable(format!(
+                "Start {} is not on a dial of size {}",
+                self.start, self.size
+            )));
         }
-        step
+        Ok(())
     }
 
-    /// Update code with step, wrapping in [0, 100> and count the number of zero-passes
+    /// Update code with step, wrapping in [0, size> and count the number of zero-passes
     ///
     /// Note that if `code` starts at 0, this won't be counted!
-    fn wrap_step(code: i32, step: i32) -> (i32, u64) {
-        let mut new_code = code;
+    fn wrap_step(&self, code: i32, step: i32) -> Result<(i32, u64), SolveError> {
+        let mut new_code = code
+            .checked_add(step)
+            .ok_or_else(|| SolveError::overflow("Dial position"))?;
         let mut zeros_count;
 
         if step >= 0 {
-            new_code += step;
-            zeros_count = new_code / 100; // Simply divide (rounding down is implicit)
-            new_code = new_code.rem_euclid(100);
+            zeros_count = new_code / self.size; // Simply divide (rounding down is implicit)
+            new_code = new_code.rem_euclid(self.size);
         } else {
-            new_code += step;
             // Division quotient (will equal -1 when e.g. `new_code = -1`)
-            zeros_count = new_code.div_euclid(100).abs();
+            zeros_count = new_code.div_euclid(self.size).abs();
             if code == 0 {
                 zeros_count -= 1; // Don't count when we started at zero already
             }
-            new_code = new_code.rem_euclid(100);
+            new_code = new_code.rem_euclid(self.size);
             if new_code == 0 {
                 zeros_count += 1; // Also count the final 0
             }
         }
-        (new_code, zeros_count.try_into().unwrap())
+        Ok((new_code, zeros_count.try_into().unwrap()))
     }
 }
 
+/// Registration of the solver for day 1
+pub const INFO: DayInfo = DayInfo {
+    day: 1,
+    title: "Secret Entrance",
+    implemented: true,
+    params: &[
+        ParamSpec {
+            name: "size",
+            description: "Number of positions on the dial",
+            default: 100,
+            min: 1,
+            max: 1_000_000,
+            below: None,
+        },
+        ParamSpec {
+            name: "start",
+            description: "Position the dial starts at, must be below `size`",
+            default: 50,
+            min: 0,
+            max: 999_999,
+            below: Some("size"),
+        },
+    ],
+    solver: |params: &Params| {
+        Box::new(Day01 {
+            size: params.get("size"),
+            start: params.get("start"),
+        })
+    },
+};
+
 impl Solution for Day01 {
+    type Parsed = Vec<i32>;
+
+    fn parse(&self, input: &str, _trace: &Trace) -> Result<Self::Parsed, SolveError> {
+        Self::read_steps(input)
+    }
+
     /// Part 1 solution
-    fn run_part_1(&self, input_file: PathBuf) -> Outcome {
+    fn part_1(&self, steps: &Self::Parsed, trace: &Trace) -> SolveResult {
+        self.check_start()?;
         let mut zeros_count = 0;
 
-        self.get_file_reader(input_file)
-            .lines()
-            .fold(50, |acc, line| {
-                let step = Self::step_to_number(&line.unwrap());
-                let next = (acc + step).rem_euclid(100);
-                if next == 0 {
-                    zeros_count += 1;
-                }
-                next
-            });
+        steps.iter().try_fold(self.start, |acc, &step| {
+            let next = acc
+                .checked_add(step)
+                .ok_or_else(|| SolveError::overflow("Dial position"))?
+                .rem_euclid(self.size);
+            if next == 0 {
+                zeros_count += 1;
+            }
+            trace.log(Level::Debug, || format!("Step {step:+}: dial at {next}"));
+            Ok::<_, SolveError>(next)
+        })?;
 
-        Outcome::U64(zeros_count)
+        Ok(Outcome::U64(zeros_count))
     }
 
     /// Part 2 solution
-    fn run_part_2(&self, input_file: PathBuf) -> Outcome {
-        let (_final_code, zeros) =
-            self.get_file_reader(input_file)
-                .lines()
-                .fold((50, 0_u64), |acc, line| {
-                    let (code, zeros) = acc;
-                    let step = Self::step_to_number(&line.unwrap());
-                    let (next_code, extra_zeros) = Self::wrap_step(code, step);
-                    (next_code, zeros + extra_zeros)
-                });
-        Outcome::U64(zeros)
+    fn part_2(&self, steps: &Self::Parsed, trace: &Trace) -> SolveResult {
+        self.check_start()?;
+        let (_final_code, zeros) = steps.iter().try_fold((self.start, 0_u64), |acc, &step| {
+            let (code, zeros) = acc;
+            let (next_code, extra_zeros) = self.wrap_step(code, step)?;
+            trace.log(Level::Debug, || {
+                format!("Step {step:+}: dial at {next_code}, passed zero {extra_zeros} times")
+            });
+            let zeros = zeros
+                .checked_add(extra_zeros)
+                .ok_or_else(|| SolveError::overflow("Number of zero-passes"))?;
+            Ok::<_, SolveError>((next_code, zeros))
+        })?;
+        Ok(Outcome::U64(zeros))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::fs;
 
     #[test]
     fn step_to_number() {
-        assert_eq!(Day01::step_to_number("R4"), 4);
-        assert_eq!(Day01::step_to_number("L11"), -11);
+        assert_eq!(Day01::step_to_number("R4").unwrap(), 4);
+        assert_eq!(Day01::step_to_number("L11").unwrap(), -11);
     }
 
     #[test]
-    fn part_1_sample() {
-        let solver = Day01 {};
-        let result = solver.run_part_1(PathBuf::from("tests/day_01/sample.txt"));
-        assert_eq!(result, Outcome::U64(3));
+    fn step_to_number_invalid() {
+        let err = Day01::step_to_number("X4").unwrap_err();
+        assert_eq!(err.to_string(), "column 1: Expected `L` or `R`, got `X`");
+        let err = Day01::step_to_number("R4a").unwrap_err();
+        assert_eq!(err.to_string(), "column 2: Expected a number, got `4a`");
+        assert!(Day01::step_to_number("").is_err());
     }
 
     #[test]
     fn wrap_step() {
+        let dial = Day01 {
+            size: 100,
+            start: 50,
+        };
         // Sample:
-        assert_eq!(Day01::wrap_step(50, -68), (82, 1));
-        assert_eq!(Day01::wrap_step(82, -30), (52, 0));
-        assert_eq!(Day01::wrap_step(52, 48), (0, 1));
-        assert_eq!(Day01::wrap_step(0, -5), (95, 0));
-        assert_eq!(Day01::wrap_step(95, 60), (55, 1));
-        assert_eq!(Day01::wrap_step(55, -55), (0, 1));
-        assert_eq!(Day01::wrap_step(0, -1), (99, 0));
-        assert_eq!(Day01::wrap_step(99, -99), (0, 1));
-        assert_eq!(Day01::wrap_step(0, 14), (14, 0));
-        assert_eq!(Day01::wrap_step(14, -82), (32, 1));
+        assert_eq!(dial.wrap_step(50, -68).unwrap(), (82, 1));
+        assert_eq!(dial.wrap_step(82, -30).unwrap(), (52, 0));
+        assert_eq!(dial.wrap_step(52, 48).unwrap(), (0, 1));
+        assert_eq!(dial.wrap_step(0, -5).unwrap(), (95, 0));
+        assert_eq!(dial.wrap_step(95, 60).unwrap(), (55, 1));
+        assert_eq!(dial.wrap_step(55, -55).unwrap(), (0, 1));
+        assert_eq!(dial.wrap_step(0, -1).unwrap(), (99, 0));
+        assert_eq!(dial.wrap_step(99, -99).unwrap(), (0, 1));
+        assert_eq!(dial.wrap_step(0, 14).unwrap(), (14, 0));
+        assert_eq!(dial.wrap_step(14, -82).unwrap(), (32, 1));
 
         // Increment:
-        assert_eq!(Day01::wrap_step(0, 10), (10, 0));
-        assert_eq!(Day01::wrap_step(0, 101), (1, 1));
-        assert_eq!(Day01::wrap_step(80, 20), (0, 1));
-        assert_eq!(Day01::wrap_step(80, 25), (5, 1));
-        assert_eq!(Day01::wrap_step(50, 1000), (50, 10));
+        assert_eq!(dial.wrap_step(0, 10).unwrap(), (10, 0));
+        assert_eq!(dial.wrap_step(0, 101).unwrap(), (1, 1));
+        assert_eq!(dial.wrap_step(80, 20).unwrap(), (0, 1));
+        assert_eq!(dial.wrap_step(80, 25).unwrap(), (5, 1));
+        assert_eq!(dial.wrap_step(50, 1000).unwrap(), (50, 10));
 
         // Decrement:
-        assert_eq!(Day01::wrap_step(50, -30), (20, 0));
-        assert_eq!(Day01::wrap_step(1, -2), (99, 1));
-        assert_eq!(Day01::wrap_step(1, -102), (99, 2));
-        assert_eq!(Day01::wrap_step(0, -10), (90, 0));
-        assert_eq!(Day01::wrap_step(0, -110), (90, 1));
-        assert_eq!(Day01::wrap_step(50, -1000), (50, 10));
+        assert_eq!(dial.wrap_step(50, -30).unwrap(), (20, 0));
+        assert_eq!(dial.wrap_step(1, -2).unwrap(), (99, 1));
+        assert_eq!(dial.wrap_step(1, -102).unwrap(), (99, 2));
+        assert_eq!(dial.wrap_step(0, -10).unwrap(), (90, 0));
+        assert_eq!(dial.wrap_step(0, -110).unwrap(), (90, 1));
+        assert_eq!(dial.wrap_step(50, -1000).unwrap(), (50, 10));
+    }
+
+    #[test]
+    fn part_1_sample_trace() {
+        let solver = Day01 {
+            size: 100,
+            start: 50,
+        };
+        let input = fs::read_to_string("tests/day_01/sample.txt").unwrap();
+        let trace = Trace::to_memory(2);
+        solver.run_part_1(&input, &trace).unwrap();
+        let messages = trace.take_messages();
+        assert_eq!(messages.len(), 10);
+        assert_eq!(messages[0], "Step -68: dial at 82");
+        assert_eq!(messages[2], "Step +48: dial at 0");
+    }
+
+    #[test]
+    fn start_beyond_dial() {
+        let err = INFO
+            .create(&[String::from("size=10"), String::from("start=10")])
+            .err()
+            .unwrap();
+        assert_eq!(err, "Value for `start` must be below `size` (10), got 10");
+        assert!(INFO.create(&[String::from("start=99")]).is_ok());
+    }
+
+    #[test]
+    fn part_1_overflow() {
+        let solver = Day01 {
+            size: 100,
+            start: 50,
+        };
+        let err = solver
+            .run_part_1("R2147483647\n", &Trace::disabled())
+            .unwrap_err();
+        assert_eq!(err.to_string(), "Overflow: Dial position does not fit");
     }
 
     #[test]
-    fn part_2_sample() {
-        let solver = Day01 {};
-        let result = solver.run_part_2(PathBuf::from("tests/day_01/sample.txt"));
-        assert_eq!(result, Outcome::U64(6));
+    fn part_2_overflow() {
+        let solver = Day01 {
+            size: 100,
+            start: 50,
+        };
+        let err = solver
+            .run_part_2("R2147483647\n", &Trace::disabled())
+            .unwrap_err();
+        assert_eq!(err.to_string(), "Overflow: Dial position does not fit");
     }
 }
@@ -1,34 +1,28 @@
-use crate::shared::{Outcome, Solution};
+use crate::days::DayInfo;
+use crate::shared::{CheckedIterator, Outcome, Solution, SolveError, SolveResult, Trace};
 use std::collections::HashSet;
-use std::io::BufRead;
 use std::ops::RangeInclusive;
-use std::path::PathBuf;
 
 pub struct Day02;
 
 impl Day02 {
-    /// Shared solution logic for both part 1 and 2
-    fn sum_invalid_ids_in_ranges(&self, input_file: PathBuf, only_two: bool) -> Outcome {
-        let first_line = self
-            .get_file_reader(input_file)
+    /// Parse the comma separated ranges on the first line
+    fn parse_ranges(&self, input: &str) -> Result<Vec<RangeInclusive<u64>>, SolveError> {
+        let first_line = input
             .lines()
             .next()
-            .unwrap()
-            .unwrap();
-
-        let func = if only_two {
-            Self::sum_invalid_ids_doubles
-        } else {
-            |range| Self::find_invalid_ids_any(range).iter().sum()
-        };
-
-        let sum: u64 = first_line
-            .split(',')
-            .map(|line| self.get_range_from_line(line))
-            .map(func)
-            .sum();
-
-        Outcome::U64(sum)
+            .ok_or_else(|| SolveError::parse("Input is empty"))?;
+
+        let mut ranges: Vec<RangeInclusive<u64>> = Vec::new();
+        let mut column = 1; // Track the position in the line for error messages
+        for segment in first_line.split(',') {
+            let range = self
+                .get_range_from_line(segment)
+                .map_err(|err| err.on_line(1).at_column(column))?;
+            ranges.push(range);
+            column += segment.len() + 1;
+        }
+        Ok(ranges)
     }
 
     // /// Parse an ID range, e.g. "11-22" becomes (11, 22)
@@ -69,19 +63,21 @@ impl Day02 {
     }
 
     /// In a provided product range, sum the invalid ids (doubled digit group)
-    fn sum_invalid_ids_doubles(range: RangeInclusive<u64>) -> u64 {
+    ///
+    /// Gives `None` if the sum overflows.
+    fn sum_invalid_ids_doubles(range: RangeInclusive<u64>) -> Option<u64> {
         let mut num = *range.start();
-        let mut sum = 0;
+        let mut sum: u64 = 0;
         loop {
             num = Self::get_next_or_current_invalid_id_doubles(num);
             if !range.contains(&num) {
                 break;
             }
-            sum += num;
+            sum = sum.checked_add(num)?;
 
             num += 1; // Continue
         }
-        sum
+        Some(sum)
     }
 
     /// In a provided product range, find the invalid ids (any duplicate groups)
@@ -123,13 +119,41 @@ impl Day02 {
     }
 }
 
+/// Registration of the solver for day 2
+pub const INFO: DayInfo = DayInfo {
+    day: 2,
+    title: "Gift Shop",
+    implemented: true,
+    params: &[],
+    solver: |_| Box::new(Day02),
+};
+
 impl Solution for Day02 {
-    fn run_part_1(&self, input_file: PathBuf) -> Outcome {
-        self.sum_invalid_ids_in_ranges(input_file, true)
+    type Parsed = Vec<RangeInclusive<u64>>;
+
+    fn parse(&self, input: &str, _trace: &Trace) -> Result<Self::Parsed, SolveError> {
+        self.parse_ranges(input)
+    }
+
+    fn part_1(&self, ranges: &Self::Parsed, _trace: &Trace) -> SolveResult {
+        let sum = ranges
+            .iter()
+            .cloned()
+            .map(Self::sum_invalid_ids_doubles)
+            .collect::<Option<Vec<u64>>>()
+            .ok_or_else(|| SolveError::overflow("Sum of invalid IDs"))?
+            .into_iter()
+            .checked_sum("Sum of invalid IDs")?;
+        Ok(Outcome::U64(sum))
     }
 
-    fn run_part_2(&self, input_file: PathBuf) -> Outcome {
-        self.sum_invalid_ids_in_ranges(input_file, false)
+    fn part_2(&self, ranges: &Self::Parsed, _trace: &Trace) -> SolveResult {
+        let sum = ranges
+            .iter()
+            .cloned()
+            .flat_map(Self::find_invalid_ids_any)
+            .checked_sum("Sum of invalid IDs")?;
+        Ok(Outcome::U64(sum))
     }
 }
 
@@ -161,13 +185,6 @@ mod tests {
         );
     }
 
-    #[test]
-    fn part_1_sample() {
-        let solver = Day02 {};
-        let result = solver.run_part_1(PathBuf::from("tests/day_02/sample.txt"));
-        assert_eq!(result, Outcome::U64(1227775554));
-    }
-
     #[test]
     fn find_invalid_ids_any() {
         // Manual:
@@ -194,13 +211,6 @@ mod tests {
         }
     }
 
-    #[test]
-    fn part_2_sample() {
-        let solver = Day02 {};
-        let result = solver.run_part_2(PathBuf::from("tests/day_02/sample.txt"));
-        assert_eq!(result, Outcome::U64(4174379265));
-    }
-
     // Alternative brute-force methods, useful for testing:
 
     #[test]
@@ -1,36 +1,99 @@
-use crate::shared::{Outcome, Solution};
-use std::io::BufRead;
-use std::path::PathBuf;
+use crate::days::DayInfo;
+use crate::shared::{
+    CheckedIterator, Execution, Outcome, ParamSpec, Params, Solution, SolveError, SolveResult,
+    Trace,
+};
 
-pub struct Day03;
+pub struct Day03 {
+    pub digits_1: usize, // Number of batteries to turn on per bank, for each part
+    pub digits_2: usize,
+}
+
+/// Registration of the solver for day 3
+pub const INFO: DayInfo = DayInfo {
+    day: 3,
+    title: "Lobby",
+    implemented: true,
+    params: &[
+        ParamSpec {
+            name: "digits_1",
+            description: "Number of digits to pick per line in part 1",
+            default: 2,
+            min: 1,
+            max: 19, // Larger numbers won't fit in a `u64`
+            below: None,
+        },
+        ParamSpec {
+            name: "digits_2",
+            description: "Number of digits to pick per line in part 2",
+            default: 12,
+            min: 1,
+            max: 19,
+            below: None,
+        },
+    ],
+    solver: |params: &Params| {
+        Box::new(Day03 {
+            digits_1: params.get("digits_1"),
+            digits_2: params.get("digits_2"),
+        })
+    },
+};
 
 impl Solution for Day03 {
-    fn run_part_1(&self, input_file: PathBuf) -> Outcome {
-        self.run_with_digits(input_file, 2)
+    type Parsed = Vec<Vec<u8>>;
+
+    fn parse(&self, input: &str, _trace: &Trace) -> Result<Self::Parsed, SolveError> {
+        input
+            .lines()
+            .enumerate()
+            .map(|(idx, line)| Self::parse_line(line).map_err(|err| err.on_line(idx + 1)))
+            .collect()
     }
 
-    fn run_part_2(&self, input_file: PathBuf) -> Outcome {
-        self.run_with_digits(input_file, 12)
+    fn part_1(&self, banks: &Self::Parsed, _trace: &Trace) -> SolveResult {
+        Self::run_with_digits(banks, self.digits_1, Execution::DEFAULT)
+    }
+
+    fn part_2(&self, banks: &Self::Parsed, _trace: &Trace) -> SolveResult {
+        Self::run_with_digits(banks, self.digits_2, Execution::DEFAULT)
     }
 }
 
 impl Day03 {
-    fn run_with_digits(&self, input_file: PathBuf, digits: usize) -> Outcome {
-        let sum: u64 = self
-            .get_file_reader(input_file)
-            .lines()
-            .map(|line| Self::parse_line(&line.unwrap()))
-            .map(|list| Self::make_highest_number(&list, digits))
-            .sum();
+    fn run_with_digits(banks: &[Vec<u8>], digits: usize, execution: Execution) -> SolveResult {
+        let numbers = execution.map_indices(banks.len(), |idx| {
+            let list = &banks[idx];
+            if list.len() < digits {
+                return Err(SolveError::parse(format!(
+                    "Expected at least {digits} digits, got {}",
+                    list.len()
+                ))
+                .on_line(idx + 1));
+            }
+            Ok(Self::make_highest_number(list, digits))
+        });
+        let sum = numbers
+            .into_iter()
+            .collect::<Result<Vec<u64>, _>>()?
+            .into_iter()
+            .checked_sum("Total joltage")?;
 
-        Outcome::U64(sum)
+        Ok(Outcome::U64(sum))
     }
 
     /// Turn line of number characters into a vector of numbers
-    fn parse_line(line: &str) -> Vec<u8> {
+    fn parse_line(line: &str) -> Result<Vec<u8>, SolveError> {
         const RADIX: u32 = 10;
         line.chars()
-            .map(|c| c.to_digit(RADIX).unwrap().try_into().unwrap())
+            .enumerate()
+            .map(|(idx, c)| match c.to_digit(RADIX) {
+                Some(digit @ 1..=9) => Ok(digit.try_into().unwrap()),
+                _ => Err(
+                    SolveError::parse(format!("Expected a digit 1-9, got `{c}`"))
+                        .at_column(idx + 1),
+                ),
+            })
             .collect()
     }
 
@@ -72,6 +135,14 @@ impl Day03 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_parse_line() {
+        assert_eq!(Day03::parse_line("1928").unwrap(), vec![1, 9, 2, 8]);
+        let err = Day03::parse_line("12x4").unwrap_err();
+        assert_eq!(err.to_string(), "column 3: Expected a digit 1-9, got `x`");
+    }
 
     #[test]
     fn test_make_highest_number() {
@@ -98,6 +169,29 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parallel_same_as_sequential() {
+        let input = fs::read_to_string("tests/day_03/sample.txt").unwrap();
+        let banks = Day03 {
+            digits_1: 2,
+            digits_2: 12,
+        }
+        .parse(&input, &Trace::disabled())
+        .unwrap();
+        for digits in [2, 12] {
+            assert_eq!(
+                Day03::run_with_digits(&banks, digits, Execution::Parallel).unwrap(),
+                Day03::run_with_digits(&banks, digits, Execution::Sequential).unwrap()
+            );
+        }
+        let error = |execution| {
+            Day03::run_with_digits(&banks, 16, execution)
+                .unwrap_err()
+                .to_string()
+        };
+        assert_eq!(error(Execution::Parallel), error(Execution::Sequential));
+    }
+
     // /// Alternative solution based on slow brute-forcing
     // fn make_highest_number_brute_force(list: &Vec<u8>) -> u8 {
     //     let mut highest: u8 = 0;
@@ -111,18 +205,4 @@ mod tests {
     //     }
     //     highest
     // }
-
-    #[test]
-    fn test_part_1_sample() {
-        let solver = Day03 {};
-        let result = solver.run_part_1(PathBuf::from("tests/day_03/sample.txt"));
-        assert_eq!(result, Outcome::U64(357));
-    }
-
-    #[test]
-    fn test_part_2_sample() {
-        let solver = Day03 {};
-        let result = solver.run_part_2(PathBuf::from("tests/day_03/sample.txt"));
-        assert_eq!(result, Outcome::U64(3_121_910_778_619));
-    }
 }
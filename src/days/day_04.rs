@@ -1,30 +1,37 @@
-use crate::shared::{Grid, Outcome, RowCol, Solution};
-use std::io::BufRead;
-use std::path::PathBuf;
+use crate::days::DayInfo;
+use crate::shared::{
+    Cells, DenseGrid, Neighbourhood, Outcome, RowCol, Solution, SolveError, SolveResult, Trace,
+};
 
 pub struct Day04;
 
-/// 8 possible steps for surrounding positions
-static SURROUNDING: [RowCol; 8] = [
-    RowCol { row: -1, col: 0 },
-    RowCol { row: -1, col: 1 },
-    RowCol { row: 0, col: 1 },
-    RowCol { row: 1, col: 1 },
-    RowCol { row: 1, col: 0 },
-    RowCol { row: 1, col: -1 },
-    RowCol { row: 0, col: -1 },
-    RowCol { row: -1, col: -1 },
-];
+/// Registration of the solver for day 4
+pub const INFO: DayInfo = DayInfo {
+    day: 4,
+    title: "Printing Department",
+    implemented: true,
+    params: &[],
+    solver: |_| Box::new(Day04),
+};
 
 impl Solution for Day04 {
-    fn run_part_1(&self, input_file: PathBuf) -> Outcome {
-        let grid = Grid::from(self.get_file_reader(input_file).lines());
-        let count = Self::find_accessible_locations(&grid).count();
-        Outcome::U64(count.try_into().unwrap())
+    type Parsed = DenseGrid<bool>; // Whether a location holds a roll of paper
+
+    fn parse(&self, input: &str, _trace: &Trace) -> Result<DenseGrid<bool>, SolveError> {
+        DenseGrid::parse(input.lines(), |symbol| match symbol {
+            '@' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        })
     }
 
-    fn run_part_2(&self, input_file: PathBuf) -> Outcome {
-        let mut grid = Grid::from(self.get_file_reader(input_file).lines());
+    fn part_1(&self, grid: &DenseGrid<bool>, _trace: &Trace) -> SolveResult {
+        let count = Self::find_accessible_locations(grid).count();
+        Ok(Outcome::U64(count.try_into().unwrap()))
+    }
+
+    fn part_2(&self, grid: &DenseGrid<bool>, _trace: &Trace) -> SolveResult {
+        let mut grid = grid.clone(); // Rolls are removed from the grid
         let mut removed = 0;
 
         loop {
@@ -33,45 +40,21 @@ impl Solution for Day04 {
                 break;
             }
             for loc in to_be_removed {
-                grid.remove_item(loc);
+                grid[loc] = false;
                 removed += 1;
             }
         }
-        Outcome::U64(removed)
+        Ok(Outcome::U64(removed))
     }
 }
 
 impl Day04 {
-    fn find_accessible_locations(grid: &Grid) -> impl Iterator<Item = RowCol> {
-        // Count number of items in the grid that have less than 4 surrounding items
-        grid.items
-            .iter()
-            .filter(|&(&loc, _)| {
-                SURROUNDING
-                    .iter()
-                    .filter(|&&step| grid.items.contains_key(&(loc + step)))
-                    .count()
-                    < 4
+    fn find_accessible_locations(grid: &DenseGrid<bool>) -> impl Iterator<Item = RowCol> {
+        // Count number of rolls in the grid that have less than 4 surrounding rolls
+        grid.iter()
+            .filter(|&(loc, &roll)| {
+                roll && grid.count_neighbours_matching(loc, &Neighbourhood::Moore, |&r| r) < 4
             })
-            .map(|(&loc, _)| loc)
-    }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_1_sample() {
-        let solver = Day04 {};
-        let result = solver.run_part_1(PathBuf::from("tests/day_04/sample.txt"));
-        assert_eq!(result, Outcome::U64(13));
-    }
-
-    #[test]
-    fn test_part_2_sample() {
-        let solver = Day04 {};
-        let result = solver.run_part_2(PathBuf::from("tests/day_04/sample.txt"));
-        assert_eq!(result, Outcome::U64(43));
+            .map(|(loc, _)| loc)
     }
 }
@@ -1,52 +1,95 @@
-use crate::shared::{Outcome, Solution};
-use std::io::BufRead;
+use crate::days::DayInfo;
+use crate::shared::{Outcome, Solution, SolveError, SolveResult, Trace};
 use std::ops::RangeInclusive;
-use std::path::PathBuf;
 
 type Range = RangeInclusive<u64>;
 
 pub struct Day05;
 
+/// Ranges of fresh ingredients and the available ingredients
+pub struct Database {
+    ranges: Vec<Range>,
+    ingredients: Vec<u64>,
+}
+
+/// Registration of the solver for day 5
+pub const INFO: DayInfo = DayInfo {
+    day: 5,
+    title: "Cafeteria",
+    implemented: true,
+    params: &[],
+    solver: |_| Box::new(Day05),
+};
+
 impl Solution for Day05 {
-    fn run_part_1(&self, input_file: PathBuf) -> Outcome {
-        let mut lines = self.get_file_reader(input_file).lines();
-        let ranges = self.make_ranges(lines.by_ref());
-        let count = lines
-            .map(|line| line.unwrap().parse::<u64>().unwrap())
-            .filter(|number| ranges.iter().any(|range| range.contains(number)))
+    type Parsed = Database;
+
+    fn parse(&self, input: &str, _trace: &Trace) -> Result<Database, SolveError> {
+        let mut lines = input.lines().enumerate();
+        let ranges = self.make_ranges(lines.by_ref())?;
+
+        let ingredients = lines
+            .map(|(idx, line)| {
+                line.parse().map_err(|_| {
+                    SolveError::parse(format!("Expected a number, got `{line}`")).on_line(idx + 1)
+                })
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Database {
+            ranges,
+            ingredients,
+        })
+    }
+
+    fn part_1(&self, database: &Database, _trace: &Trace) -> SolveResult {
+        let count = database
+            .ingredients
+            .iter()
+            .filter(|number| database.ranges.iter().any(|range| range.contains(number)))
             .count();
 
-        Outcome::U64(count.try_into().unwrap())
+        Ok(Outcome::U64(count as u64))
     }
 
-    fn run_part_2(&self, input_file: PathBuf) -> Outcome {
-        let mut lines = self.get_file_reader(input_file).lines();
-        let new_ranges = self.make_ranges(lines.by_ref());
-
+    fn part_2(&self, database: &Database, _trace: &Trace) -> SolveResult {
         let mut combined_ranges: Vec<Range> = Vec::new();
 
-        for range in new_ranges {
+        for range in database.ranges.iter().cloned() {
             combined_ranges = Self::combine_inclusive_range(combined_ranges, range);
         }
 
-        Outcome::U64(Self::count_ranges_list(combined_ranges))
+        Ok(Outcome::U128(Self::count_ranges_list(combined_ranges)))
     }
 }
 
 impl Day05 {
     /// Get ranges from the first part of the input file
-    fn make_ranges(
+    ///
+    /// Lines are consumed up to and including the first empty line.
+    fn make_ranges<'a>(
         &self,
-        lines: &mut std::io::Lines<std::io::BufReader<std::fs::File>>,
-    ) -> Vec<Range> {
-        lines
-            .take_while(|line| !line.as_ref().unwrap().is_empty())
-            .map(|line| self.get_range_from_line(&line.unwrap()))
-            .collect()
+        lines: &mut impl Iterator<Item = (usize, &'a str)>,
+    ) -> Result<Vec<Range>, SolveError> {
+        let mut ranges = Vec::new();
+        for (idx, line) in lines {
+            if line.is_empty() {
+                break;
+            }
+            ranges.push(
+                self.get_range_from_line(line)
+                    .map_err(|err| err.on_line(idx + 1))?,
+            );
+        }
+        Ok(ranges)
     }
 
     /// Merge a set of existing ranges with a new one
-    fn combine_inclusive_range(mut ranges: Vec<Range>, new_range: Range) -> Vec<Range> {
+    ///
+    /// Overlapping and directly adjacent ranges are combined into one, so the result never
+    /// contains duplicate values.
+    #[must_use]
+    pub fn combine_inclusive_range(mut ranges: Vec<Range>, new_range: Range) -> Vec<Range> {
         if new_range.end() < new_range.start() {
             return ranges; // Invalid or empty range
         }
@@ -54,8 +97,8 @@ impl Day05 {
             if this_range.contains(new_range.start()) && this_range.contains(new_range.end()) {
                 // `new_range` falls entirely within `this_range`, so no need to do anything
                 return ranges;
-            } else if *new_range.end() >= this_range.start() - 1
-                && *new_range.start() <= this_range.end() + 1
+            } else if *new_range.end() >= this_range.start().saturating_sub(1)
+                && *new_range.start() <= this_range.end().saturating_add(1)
             {
                 // We have any amount of overlap of `new_range` and `this_range`, but not full
                 // Ranges that are directly adjacent will also be merged together
@@ -74,8 +117,15 @@ impl Day05 {
     }
 
     /// Count all the possible values in a list of ranges
-    fn count_ranges_list(ranges: Vec<Range>) -> u64 {
-        ranges.into_iter().map(|r| r.end() - r.start() + 1).sum()
+    ///
+    /// The ranges should not overlap, as created by [`Day05::combine_inclusive_range`].
+    /// The count is a `u128`, since a range can cover all `u64` values.
+    #[must_use]
+    pub fn count_ranges_list(ranges: Vec<Range>) -> u128 {
+        ranges
+            .into_iter()
+            .map(|r| u128::from(r.end() - r.start()) + 1)
+            .sum()
         // Avoid `count()` as it will perform the actual iteration, which we don't need
     }
 }
@@ -86,10 +136,12 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_part_1_sample() {
+    fn test_part_1_invalid_range() {
         let solver = Day05 {};
-        let result = solver.run_part_1(PathBuf::from("tests/day_05/sample.txt"));
-        assert_eq!(result, Outcome::U64(3));
+        let err = solver
+            .run_part_1("3-5\n10:14\n\n1\n", &Trace::disabled())
+            .unwrap_err();
+        assert_eq!(err.to_string(), "line 2: Expected `a-b` range, got `10:14`");
     }
 
     #[allow(clippy::reversed_empty_ranges)]
@@ -132,36 +184,17 @@ mod tests {
     }
 
     #[test]
-    fn test_part_2_sample() {
-        let solver = Day05 {};
-        let result = solver.run_part_2(PathBuf::from("tests/day_05/sample.txt"));
-        assert_eq!(result, Outcome::U64(14));
-    }
-
-    #[test]
-    fn test_part_2_custom() {
-        let solver = Day05 {};
-        let result = solver.run_part_2(PathBuf::from("tests/day_05/custom.txt"));
-        // In our files, the optimized ranges are:
-        // 100-170
-        // 5-35
-        // 990-1100
-        assert_eq!(
-            result,
-            Outcome::U64(170 - 100 + 1 + 35 - 5 + 1 + 1100 - 990 + 1)
-        );
-    }
-
-    #[allow(clippy::identity_op)]
-    #[test]
-    fn test_part_2_big() {
-        let solver = Day05 {};
-        let result = solver.run_part_2(PathBuf::from("tests/day_05/big.txt"));
-        assert_eq!(
-            result,
-            Outcome::U64(
-                213743501161200 - 213743501161200 + 1 + 219471291456959 - 213743501161201 + 1
-            )
-        );
+    fn combine_ranges_at_limits() {
+        let mut ranges = Day05::combine_inclusive_range(vec![], 10..=20);
+        ranges = Day05::combine_inclusive_range(ranges, 0..=5); // Starts at 0
+        assert_eq!(ranges, vec![10..=20, 0..=5]);
+        ranges = Day05::combine_inclusive_range(ranges, 0..=9);
+        assert_eq!(ranges, vec![0..=20]);
+
+        let mut ranges = Day05::combine_inclusive_range(vec![], 10..=u64::MAX);
+        ranges = Day05::combine_inclusive_range(ranges, 5..=15); // Existing ends at `u64::MAX`
+        assert_eq!(ranges, vec![5..=u64::MAX]);
+        ranges = Day05::combine_inclusive_range(ranges, 0..=4);
+        assert_eq!(ranges, vec![0..=u64::MAX]);
     }
 }
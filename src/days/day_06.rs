@@ -1,13 +1,28 @@
-use crate::shared::{Outcome, Solution};
-use std::io::BufRead;
+use crate::days::DayInfo;
+use crate::shared::{CheckedIterator, Outcome, Solution, SolveError, SolveResult, Trace};
 use std::ops::Range;
-use std::path::PathBuf;
 use std::str::Chars;
 
 pub struct Day06;
 
+/// Registration of the solver for day 6
+pub const INFO: DayInfo = DayInfo {
+    day: 6,
+    title: "Trash Compactor",
+    implemented: true,
+    params: &[],
+    solver: |_| Box::new(Day06),
+};
+
 impl Solution for Day06 {
-    fn run_part_1(&self, input_file: PathBuf) -> Outcome {
+    /// The parts read the numbers differently (by row or by column), so only split the lines
+    type Parsed = Vec<String>;
+
+    fn parse(&self, input: &str, _trace: &Trace) -> Result<Self::Parsed, SolveError> {
+        Ok(input.lines().map(String::from).collect())
+    }
+
+    fn part_1(&self, lines: &Self::Parsed, _trace: &Trace) -> SolveResult {
         // Matrix of numbers, organized like numbers[<column>][<row>]
         let mut numbers: Vec<Vec<u64>> = Vec::new();
 
@@ -15,42 +30,64 @@ impl Solution for Day06 {
         let mut operators: Vec<char> = Vec::new();
 
         // Do old-fashioned loop because the if-else is hard to manage in an expression
-        for line in self.get_file_reader(input_file).lines() {
-            let line = line.unwrap();
+        for (idx, line) in lines.iter().enumerate() {
             let row: Result<Vec<_>, _> = line.split_whitespace().map(str::parse::<u64>).collect();
             if let Ok(row) = row {
                 if numbers.is_empty() {
                     numbers.resize(row.len(), Vec::new());
                 }
+                if row.len() != numbers.len() {
+                    return Err(SolveError::parse(format!(
+                        "Expected {} numbers, got {}",
+                        numbers.len(),
+                        row.len()
+                    ))
+                    .on_line(idx + 1));
+                }
                 for (column_idx, value) in row.into_iter().enumerate() {
                     numbers[column_idx].push(value);
                 }
             } else {
                 operators = line
                     .split_whitespace()
-                    .map(|bit| bit.chars().next().unwrap())
-                    .collect();
+                    .map(|bit| match bit {
+                        "+" | "*" => Ok(bit.chars().next().unwrap()),
+                        _ => Err(SolveError::parse(format!(
+                            "Expected a number or an operator, got `{bit}`"
+                        ))
+                        .on_line(idx + 1)),
+                    })
+                    .collect::<Result<_, _>>()?;
             }
         }
 
-        Outcome::U64(Self::sum_column_operations(&numbers, &operators))
+        Self::check_operators(&numbers, &operators)?;
+        Self::sum_column_operations(&numbers, &operators).map(Outcome::U64)
     }
 
-    fn run_part_2(&self, input_file: PathBuf) -> Outcome {
-        let mut lines: Vec<String> = self
-            .get_file_reader(input_file)
-            .lines()
-            .map(|line| line.unwrap())
-            .collect();
-        let max_line_length: usize = lines.iter().map(|line| line.chars().count()).max().unwrap();
+    fn part_2(&self, lines: &Self::Parsed, _trace: &Trace) -> SolveResult {
+        let mut lines: Vec<&str> = lines.iter().map(String::as_str).collect();
+        let max_line_length: usize = lines
+            .iter()
+            .map(|line| line.chars().count())
+            .max()
+            .ok_or_else(|| SolveError::parse("Input is empty"))?;
 
-        let operators_line = lines.pop().unwrap();
+        let operators_line_number = lines.len();
+        let operators_line = lines.pop().unwrap(); // Input can't be empty at this point
 
         // Build a list of the character ranges for colum
         let mut column_ranges: Vec<Range<usize>> = Vec::new(); // Note: characters, not bytes!!!
         let mut operators: Vec<char> = Vec::new(); // List of operators per column
         let mut last_idx = None;
         for (column_idx, c) in operators_line.chars().enumerate() {
+            if !matches!(c, ' ' | '+' | '*') {
+                return Err(
+                    SolveError::parse(format!("Expected an operator, got `{c}`"))
+                        .on_line(operators_line_number)
+                        .at_column(column_idx + 1),
+                );
+            }
             if c != ' ' {
                 operators.push(c);
                 if let Some(last_idx) = last_idx {
@@ -73,12 +110,15 @@ impl Solution for Day06 {
         for (column_idx, slice) in column_ranges.into_iter().enumerate() {
             // Foreach character belonging to the next column, take the next character of each of
             // the lines and combine them into strings:
-            for _ in slice {
+            for char_idx in slice {
                 let num_str: String = line_iters
                     .iter_mut()
                     .map(|it| it.next().unwrap_or(' '))
                     .collect();
-                let num: u64 = num_str.trim().parse().unwrap();
+                let num: u64 = num_str.trim().parse().map_err(|_| {
+                    SolveError::parse(format!("Expected a vertical number, got `{num_str}`"))
+                        .at_column(char_idx + 1)
+                })?;
                 numbers[column_idx].push(num);
             }
             // Advance iterators past empty spaces:
@@ -87,27 +127,42 @@ impl Solution for Day06 {
             }
         }
 
-        Outcome::U64(Self::sum_column_operations(&numbers, &operators))
+        Self::sum_column_operations(&numbers, &operators).map(Outcome::U64)
     }
 }
 
 impl Day06 {
+    /// Make sure there is an operator for every column
+    fn check_operators(columns: &[Vec<u64>], operators: &[char]) -> Result<(), SolveError> {
+        if columns.len() == operators.len() {
+            Ok(())
+        } else {
+            Err(SolveError::parse(format!(
+                "Expected {} operators, got {}",
+                columns.len(),
+                operators.len()
+            )))
+        }
+    }
+
     /// Perform a repeated math operation on a column
-    fn column_operation(column: &[u64], operator: char) -> u64 {
+    fn column_operation(column: &[u64], operator: char) -> Result<u64, SolveError> {
         match operator {
-            '+' => column.iter().sum::<u64>(),
-            '*' => column.iter().product::<u64>(),
+            '+' => column.iter().copied().checked_sum("Column sum"),
+            '*' => column.iter().copied().checked_product("Column product"),
             _ => panic!("Unrecognized character"),
         }
     }
 
     /// Sum operations on all columns at once
-    fn sum_column_operations(columns: &[Vec<u64>], operators: &[char]) -> u64 {
+    fn sum_column_operations(columns: &[Vec<u64>], operators: &[char]) -> Result<u64, SolveError> {
         columns
             .iter()
             .enumerate()
             .map(|(column_idx, column)| Self::column_operation(column, operators[column_idx]))
-            .sum()
+            .collect::<Result<Vec<u64>, _>>()?
+            .into_iter()
+            .checked_sum("Grand total")
     }
 }
 
@@ -116,16 +171,10 @@ mod tests {
     use super::*;
 
     #[test]
-    fn part_1_sample() {
-        let solver = Day06 {};
-        let result = solver.run_part_1(PathBuf::from("tests/day_06/sample.txt"));
-        assert_eq!(result, Outcome::U64(4_277_556));
-    }
-
-    #[test]
-    fn part_2_sample() {
+    fn part_1_overflow() {
         let solver = Day06 {};
-        let result = solver.run_part_2(PathBuf::from("tests/day_06/sample.txt"));
-        assert_eq!(result, Outcome::U64(3_263_827));
+        let input = "4294967296 7\n4294967296 8\n*         +\n";
+        let err = solver.run_part_1(input, &Trace::disabled()).unwrap_err();
+        assert_eq!(err.to_string(), "Overflow: Column product does not fit");
     }
 }
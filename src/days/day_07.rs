@@ -1,36 +1,62 @@
-use crate::shared::{Grid, Outcome, RowCol, Solution};
+use crate::days::DayInfo;
+use crate::shared::{
+    CheckedIterator, Grid, Level, Outcome, RowCol, Solution, SolveError, SolveResult, Trace,
+};
 use std::collections::HashMap;
-use std::io::BufRead;
-use std::path::PathBuf;
 
 pub struct Day07;
 
+/// Grid of splitters, with the location where the beam enters
+pub struct Manifold {
+    grid: Grid,
+    start: RowCol,
+}
+
+/// Registration of the solver for day 7
+pub const INFO: DayInfo = DayInfo {
+    day: 7,
+    title: "Laboratories",
+    implemented: true,
+    params: &[],
+    solver: |_| Box::new(Day07),
+};
+
 impl Solution for Day07 {
-    fn run_part_1(&self, input_file: PathBuf) -> Outcome {
-        self.count_tachyons(input_file, true)
+    type Parsed = Manifold;
+
+    fn parse(&self, input: &str, _trace: &Trace) -> Result<Manifold, SolveError> {
+        let grid = Grid::from(input.lines());
+        let start = grid.get_item_by_symbol('S').map_err(SolveError::parse)?;
+        Ok(Manifold { grid, start })
     }
 
-    fn run_part_2(&self, input_file: PathBuf) -> Outcome {
-        self.count_tachyons(input_file, false)
+    fn part_1(&self, manifold: &Manifold, trace: &Trace) -> SolveResult {
+        Self::count_tachyons(manifold, true, trace)
+    }
+
+    fn part_2(&self, manifold: &Manifold, trace: &Trace) -> SolveResult {
+        Self::count_tachyons(manifold, false, trace)
     }
 }
 
 impl Day07 {
     /// Combined solution for parts 1 and 2
-    fn count_tachyons(&self, input_file: PathBuf, splits_only: bool) -> Outcome {
-        let grid = Grid::from(self.get_file_reader(input_file).lines());
-
-        let loc_start = grid.get_item_by_symbol('S').unwrap();
+    fn count_tachyons(manifold: &Manifold, splits_only: bool, trace: &Trace) -> SolveResult {
+        let Manifold {
+            grid,
+            start: loc_start,
+        } = manifold;
 
         // Count number of splits (for part 1 only)
         let mut splits = 0;
 
         // Keep a set of laser beam x-positions together with how many paths lead there, so far
-        let mut tips: HashMap<i32, u64> = HashMap::from([(loc_start.col, 1)]);
+        // The number of paths can double on every row, so count them in 128 bits
+        let mut tips: HashMap<i32, u128> = HashMap::from([(loc_start.col, 1)]);
 
         // Walk through the next rows in the grid:
         for row in (loc_start.row + 1)..grid.rows {
-            let mut next_tips: HashMap<i32, u64> = HashMap::new();
+            let mut next_tips: HashMap<i32, u128> = HashMap::new();
 
             for (tip_col, path_count) in tips {
                 let next = RowCol::new(row, tip_col);
@@ -41,35 +67,29 @@ impl Day07 {
                     vec![0]
                 };
                 for step in steps {
-                    *next_tips.entry(tip_col + step).or_insert(0) += path_count;
+                    let count = next_tips.entry(tip_col + step).or_insert(0);
+                    *count = count
+                        .checked_add(path_count)
+                        .ok_or_else(|| SolveError::overflow("Number of timelines"))?;
                 }
             }
             tips = next_tips; // Replace tips with the new list
+
+            trace.log(Level::Debug, || {
+                let mut cols: Vec<(&i32, &u128)> = tips.iter().collect();
+                cols.sort_unstable();
+                let cols: Vec<String> = cols
+                    .into_iter()
+                    .map(|(col, count)| format!("{col} ({count}x)"))
+                    .collect();
+                format!("Row {row}: {splits} splits, tips at {}", cols.join(", "))
+            });
         }
 
-        Outcome::U64(if splits_only {
-            splits
+        Ok(if splits_only {
+            Outcome::U64(splits)
         } else {
-            tips.values().sum()
+            Outcome::U128(tips.into_values().checked_sum("Number of timelines")?)
         })
     }
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_1_sample() {
-        let solver = Day07 {};
-        let result = solver.run_part_1(PathBuf::from("tests/day_07/sample.txt"));
-        assert_eq!(result, Outcome::U64(21));
-    }
-
-    #[test]
-    fn test_part_2_sample() {
-        let solver = Day07 {};
-        let result = solver.run_part_2(PathBuf::from("tests/day_07/sample.txt"));
-        assert_eq!(result, Outcome::U64(40));
-    }
-}
@@ -1,98 +1,142 @@
-use crate::shared::{Outcome, Solution};
+use crate::days::DayInfo;
+use crate::shared::{
+    CheckedIterator, Execution, Level, Outcome, ParamSpec, Params, Solution, SolveError,
+    SolveResult, Trace,
+};
 use std::collections::HashSet;
 use std::hash::Hash;
-use std::io::BufRead;
-use std::path::PathBuf;
 
 type Point = [i32; 3];
 type Points = Vec<Point>;
 type Pair = (usize, usize);
+type Distances = Vec<(Pair, f64)>;
+type Circuits = Vec<HashSet<usize>>;
 
 pub struct Day08 {
-    pub limit: usize,
+    pub limit: usize, // Number of shortest connections to make in part 1
+}
+
+/// Registration of the solver for day 8
+pub const INFO: DayInfo = DayInfo {
+    day: 8,
+    title: "Playground",
+    implemented: true,
+    params: &[ParamSpec {
+        name: "limit",
+        description: "Number of shortest connections to make in part 1",
+        default: 1_000,
+        min: 1,
+        max: 1_000_000_000,
+        below: None,
+    }],
+    solver: |params: &Params| {
+        Box::new(Day08 {
+            limit: params.get("limit"),
+        })
+    },
+};
+
+/// Junction positions and all pairs of them, sorted by distance
+pub struct Junctions {
+    points: Points,
+    distances: Distances,
 }
 
 impl Solution for Day08 {
-    fn run_part_1(&self, input_file: PathBuf) -> Outcome {
-        let (_junctions, distances, mut circuits) = self.find_distances_and_circuits(input_file);
+    type Parsed = Junctions;
+
+    fn parse(&self, input: &str, _trace: &Trace) -> Result<Junctions, SolveError> {
+        let points: Points = input
+            .lines()
+            .enumerate()
+            .map(|(idx, line)| Self::parse_point(line).map_err(|err| err.on_line(idx + 1)))
+            .collect::<Result<_, _>>()?;
+        let distances = Self::find_closest_pairs(&points, Execution::DEFAULT);
+        Ok(Junctions { points, distances })
+    }
+
+    fn part_1(&self, junctions: &Junctions, trace: &Trace) -> SolveResult {
+        let mut circuits = Self::make_circuits(&junctions.points);
 
         // Get the shortest distances (up to limit):
-        for (pair, _dist) in distances.iter().take(self.limit) {
-            Self::merge_circuits(pair, &mut circuits);
+        for (pair, _dist) in junctions.distances.iter().take(self.limit) {
+            Self::merge_circuits(pair, &mut circuits, trace);
         }
 
         // Find the largest three circuits to multiply the size of:
         let mut circuit_sizes: Vec<u64> = circuits.iter().map(|c| c.len() as u64).collect();
         circuit_sizes.sort_by(|a, b| b.cmp(a));
 
-        let result = circuit_sizes.iter().take(3).product();
-        Outcome::U64(result)
+        let result = circuit_sizes
+            .into_iter()
+            .take(3)
+            .checked_product("Product of circuit sizes")?;
+        Ok(Outcome::U64(result))
     }
 
-    fn run_part_2(&self, input_file: PathBuf) -> Outcome {
-        let (junctions, distances, mut circuits) = self.find_distances_and_circuits(input_file);
+    fn part_2(&self, junctions: &Junctions, trace: &Trace) -> SolveResult {
+        let mut circuits = Self::make_circuits(&junctions.points);
 
-        // Get the shortest distances (up to limit):
-        for (pair, _dist) in distances {
-            Self::merge_circuits(&pair, &mut circuits);
+        // Connect the shortest distances until everything is connected:
+        for (pair, _dist) in &junctions.distances {
+            Self::merge_circuits(pair, &mut circuits, trace);
 
             if circuits.len() == 1 {
                 // Everything just became a single circuit!
-                return Outcome::U64(
-                    u64::try_from(junctions[pair.0][0]).unwrap()
-                        * u64::try_from(junctions[pair.1][0]).unwrap(),
-                );
+                let x_a = junctions.points[pair.0][0];
+                let x_b = junctions.points[pair.1][0];
+                return match (u64::try_from(x_a), u64::try_from(x_b)) {
+                    (Ok(x_a), Ok(x_b)) => Ok(Outcome::U64(x_a * x_b)),
+                    _ => Err(SolveError::Unsolvable(format!(
+                        "Cannot multiply negative coordinates {x_a} and {x_b}"
+                    ))),
+                };
             }
         }
 
-        panic!("Failed to find solution");
+        Err(SolveError::Unsolvable(String::from(
+            "Junctions never form a single circuit",
+        )))
     }
 }
 
 impl Day08 {
-    fn find_distances_and_circuits(
-        &self,
-        input_file: PathBuf,
-    ) -> (Points, Vec<(Pair, f64)>, Vec<HashSet<usize>>) {
-        let junctions: Points = self
-            .get_file_reader(input_file)
-            .lines()
-            .map(|line| {
-                let line = line.unwrap();
-                line.split(',')
-                    .map(|part| part.parse().unwrap())
-                    .collect::<Vec<i32>>()
-                    .try_into()
-                    .unwrap()
-            })
-            .collect();
-
-        // Track circuits (= groups of connected junctions):
-        let circuits: Vec<HashSet<usize>> = junctions
-            .iter()
-            .enumerate()
-            .map(|(idx, _)| HashSet::from([idx]))
-            .collect();
-
-        let distances = Self::find_closest_pairs(&junctions);
+    /// Track circuits (= groups of connected junctions), initially each junction by itself
+    fn make_circuits(points: &[Point]) -> Circuits {
+        (0..points.len()).map(|idx| HashSet::from([idx])).collect()
+    }
 
-        (junctions, distances, circuits)
+    /// Parse a point from a line like "162,817,812"
+    fn parse_point(line: &str) -> Result<Point, SolveError> {
+        let parse_error = || SolveError::parse(format!("Expected `x,y,z`, got `{line}`"));
+        line.split(',')
+            .map(|part| part.parse().map_err(|_| parse_error()))
+            .collect::<Result<Vec<i32>, _>>()?
+            .try_into()
+            .map_err(|_| parse_error())
     }
 
     /// Make a list of pairs and their distances and sort them
-    fn find_closest_pairs(points: &[Point]) -> Vec<(Pair, f64)> {
-        let mut distances: Vec<(Pair, f64)> = Vec::new();
-
-        for (i_a, point_a) in points.iter().enumerate() {
-            for (i_b, point_b) in points[(i_a + 1)..].iter().enumerate() {
-                let i_b = i_a + 1 + i_b;
-
-                distances.push(((i_a, i_b), Self::distance(point_a, point_b)));
-            }
-        }
+    fn find_closest_pairs(points: &[Point], execution: Execution) -> Distances {
+        let mut distances: Distances = execution
+            .map_indices(points.len(), |i_a| {
+                points[(i_a + 1)..]
+                    .iter()
+                    .enumerate()
+                    .map(|(i_b, point_b)| {
+                        let i_b = i_a + 1 + i_b;
+                        ((i_a, i_b), Self::distance(&points[i_a], point_b))
+                    })
+                    .collect::<Distances>()
+            })
+            .into_iter()
+            .flatten()
+            .collect();
 
         // Sort distances:
-        distances.sort_by(|(_, dist_a), (_, dist_b)| dist_a.partial_cmp(dist_b).unwrap());
+        execution.sort_by(&mut distances, |(_, dist_a), (_, dist_b)| {
+            dist_a.total_cmp(dist_b)
+        });
         distances
     }
 
@@ -115,13 +159,16 @@ impl Day08 {
     }
 
     /// From a pair, combine the two corresponding circuits
-    fn merge_circuits(pair: &Pair, circuits: &mut Vec<HashSet<usize>>) {
+    fn merge_circuits(pair: &Pair, circuits: &mut Vec<HashSet<usize>>, trace: &Trace) {
         let mut circuit_ids: Vec<usize> = [pair.0, pair.1]
             .iter()
             .map(|idx| Self::find_in_sets(idx, circuits).unwrap())
             .collect();
 
         if circuit_ids[0] == circuit_ids[1] {
+            trace.log(Level::Trace, || {
+                format!("Junctions {} and {} are already connected", pair.0, pair.1)
+            });
             return; // Nothing changes
         }
 
@@ -132,24 +179,56 @@ impl Day08 {
 
         circuits[circuit_ids[0]].extend(circuit_old);
         // Move the elements into the first circuit, inside the vector
+
+        trace.log(Level::Info, || {
+            format!(
+                "Connected junctions {} and {}: circuit of {}, {} circuits left",
+                pair.0,
+                pair.1,
+                circuits[circuit_ids[0]].len(),
+                circuits.len()
+            )
+        });
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::fs;
 
     #[test]
-    fn test_part_1_sample() {
+    fn test_part_1_sample_trace() {
         let solver = Day08 { limit: 10 };
-        let result = solver.run_part_1(PathBuf::from("tests/day_08/sample.txt"));
-        assert_eq!(result, Outcome::U64(40));
+        let input = fs::read_to_string("tests/day_08/sample.txt").unwrap();
+        let trace = Trace::to_memory(1);
+        solver.run_part_1(&input, &trace).unwrap();
+        let messages = trace.take_messages();
+        // One of the 10 shortest connections is between junctions already in the same circuit
+        assert_eq!(messages.len(), 9);
+        assert_eq!(
+            messages[0],
+            "Connected junctions 0 and 19: circuit of 2, 19 circuits left"
+        );
     }
 
     #[test]
-    fn test_part_2_sample() {
-        let solver = Day08 { limit: 10 };
-        let result = solver.run_part_2(PathBuf::from("tests/day_08/sample.txt"));
-        assert_eq!(result, Outcome::U64(25272));
+    fn parallel_same_as_sequential() {
+        let input = fs::read_to_string("tests/day_08/sample.txt").unwrap();
+        let points: Points = input
+            .lines()
+            .map(|line| Day08::parse_point(line).unwrap())
+            .collect();
+        assert_eq!(
+            Day08::find_closest_pairs(&points, Execution::Parallel),
+            Day08::find_closest_pairs(&points, Execution::Sequential)
+        );
+    }
+
+    #[test]
+    fn test_parse_point() {
+        assert_eq!(Day08::parse_point("162,817,812").unwrap(), [162, 817, 812]);
+        assert!(Day08::parse_point("162,817").is_err());
+        assert!(Day08::parse_point("162,817,a").is_err());
     }
 }
@@ -1,7 +1,6 @@
-use crate::shared::{Outcome, RowCol, Solution};
+use crate::days::DayInfo;
+use crate::shared::{Execution, Outcome, RowCol, Solution, SolveError, SolveResult, Trace};
 use std::cmp::{max, min};
-use std::io::BufRead;
-use std::path::PathBuf;
 
 /// Abstraction for a straight line (horizontal or vertical)
 #[derive(Copy, Clone)]
@@ -52,9 +51,12 @@ impl Rectangle {
     }
 
     /// Return rectangle size (including corners)
-    pub fn size(&self) -> u64 {
-        u64::try_from(self.corner_se.row - self.corner_nw.row + 1).unwrap()
-            * u64::try_from(self.corner_se.col - self.corner_nw.col + 1).unwrap()
+    pub fn size(&self) -> Result<u64, SolveError> {
+        // Corners are ordered, so neither length is negative
+        let length = |from: i32, to: i32| (i64::from(to) - i64::from(from) + 1).unsigned_abs();
+        length(self.corner_nw.row, self.corner_se.row)
+            .checked_mul(length(self.corner_nw.col, self.corner_se.col))
+            .ok_or_else(|| SolveError::overflow("Rectangle size"))
     }
 
     /// Return true if rectangle is crossed by the given line
@@ -73,45 +75,59 @@ impl Rectangle {
 
 pub struct Day09;
 
+/// Registration of the solver for day 9
+pub const INFO: DayInfo = DayInfo {
+    day: 9,
+    title: "Movie Theater",
+    implemented: true,
+    params: &[],
+    solver: |_| Box::new(Day09),
+};
+
 impl Solution for Day09 {
-    fn run_part_1(&self, input_file: PathBuf) -> Outcome {
-        let points = self.build_points(input_file);
+    type Parsed = Vec<RowCol>;
+
+    fn parse(&self, input: &str, _trace: &Trace) -> Result<Self::Parsed, SolveError> {
+        Self::build_points(input)
+    }
+
+    fn part_1(&self, points: &Self::Parsed, _trace: &Trace) -> SolveResult {
         let mut biggest: u64 = 0;
 
         // Check all combinations of points:
         for (i1, p1) in points.iter().enumerate() {
             for p2 in points.iter().skip(i1 + 1) {
                 let rect = Rectangle::new(*p1, *p2);
-                biggest = max(biggest, rect.size());
+                biggest = max(biggest, rect.size()?);
             }
         }
 
-        Outcome::U64(biggest)
+        Ok(Outcome::U64(biggest))
     }
 
-    fn run_part_2(&self, input_file: PathBuf) -> Outcome {
-        let points = self.build_points(input_file);
-
-        // Lists of all lines and the vertical ones in particular
-        let mut lines: Vec<Line> = Vec::new();
-        let mut lines_vertical: Vec<Line> = Vec::new();
-
-        for (i1, &p1) in points.iter().enumerate() {
-            let i2 = (i1 + 1) % points.len();
-            let p2 = points[i2];
-            let line = Line::new(p1, p2);
-            lines.push(line);
-            if line.vertical {
-                lines_vertical.push(line);
-            }
-        }
+    fn part_2(&self, points: &Self::Parsed, _trace: &Trace) -> SolveResult {
+        let (lines, lines_vertical) = Self::perimeter(points)?;
+        let biggest = Self::biggest_inside(points, &lines, &lines_vertical, Execution::DEFAULT)?;
+        Ok(Outcome::U64(biggest))
+    }
+}
 
-        // Try gather all pairs of points we could make in the whole set, for trial rectangles:
-        let mut biggest = 0;
-        for (i1, &p1) in points.iter().enumerate() {
+impl Day09 {
+    /// Find the biggest rectangle between two of the points that lies within the perimeter
+    ///
+    /// The pairs are split by their first point, each of which can be checked independently.
+    fn biggest_inside(
+        points: &[RowCol],
+        lines: &[Line],
+        lines_vertical: &[Line],
+        execution: Execution,
+    ) -> Result<u64, SolveError> {
+        let biggest_from = |i1: usize| {
+            let p1 = points[i1];
+            let mut biggest = 0;
             for &p2 in points.iter().skip(i1) {
                 let rect = Rectangle::new(p1, p2);
-                let size = rect.size();
+                let size = rect.size()?;
                 if size <= biggest {
                     continue; // Don't bother checking anything else, this pair is useless
                 }
@@ -121,7 +137,7 @@ impl Solution for Day09 {
                 // i)  Ray test - determine if this rect is not outside the shape entirely
                 // ii) Cross test - check if no lines of the perimeter cut through it
 
-                if Self::count_ray_intersections(&rect, &lines_vertical).is_power_of_two() {
+                if Self::count_ray_intersections(&rect, lines_vertical).is_power_of_two() {
                     continue; // Skip this `rect`, it's outside the perimeter
                 }
 
@@ -131,18 +147,47 @@ impl Solution for Day09 {
 
                 biggest = size;
             }
+            Ok(biggest)
+        };
+
+        let biggest: Vec<u64> = execution
+            .map_indices(points.len(), biggest_from)
+            .into_iter()
+            .collect::<Result<_, SolveError>>()?;
+        Ok(biggest.into_iter().max().unwrap_or(0))
+    }
+
+    /// Lines between consecutive points, and the vertical ones among them
+    fn perimeter(points: &[RowCol]) -> Result<(Vec<Line>, Vec<Line>), SolveError> {
+        // Lists of all lines and the vertical ones in particular
+        let mut lines: Vec<Line> = Vec::new();
+        let mut lines_vertical: Vec<Line> = Vec::new();
+
+        for (i1, &p1) in points.iter().enumerate() {
+            let i2 = (i1 + 1) % points.len();
+            let p2 = points[i2];
+            if p1.row != p2.row && p1.col != p2.col {
+                return Err(SolveError::parse(
+                    "Point is not in a straight line with the previous one",
+                )
+                .on_line(i2 + 1));
+            }
+            let line = Line::new(p1, p2);
+            lines.push(line);
+            if line.vertical {
+                lines_vertical.push(line);
+            }
         }
 
-        Outcome::U64(biggest)
+        Ok((lines, lines_vertical))
     }
-}
 
-impl Day09 {
     /// Build the grid from the input file
-    fn build_points(&self, input_file: PathBuf) -> Vec<RowCol> {
-        self.get_file_reader(input_file)
+    fn build_points(input: &str) -> Result<Vec<RowCol>, SolveError> {
+        input
             .lines()
-            .map(|line| RowCol::from(line.unwrap().as_str()))
+            .enumerate()
+            .map(|(idx, line)| RowCol::try_from(line).map_err(|err| err.on_line(idx + 1)))
             .collect()
     }
 
@@ -171,12 +216,17 @@ impl Day09 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::fs;
 
     #[test]
-    fn test_part_1_sample() {
-        let solver = Day09 {};
-        let result = solver.run_part_1(PathBuf::from("tests/day_09/sample.txt"));
-        assert_eq!(result, Outcome::U64(50));
+    fn test_rectangle_size() {
+        let rect = Rectangle::new(RowCol::new(7, 9), RowCol::new(3, 1));
+        assert_eq!(rect.size().unwrap(), 45);
+        let huge = Rectangle::new(
+            RowCol::new(i32::MIN, i32::MIN),
+            RowCol::new(i32::MAX, i32::MAX),
+        );
+        assert!(matches!(huge.size(), Err(SolveError::Overflow(_))));
     }
 
     #[test]
@@ -197,9 +247,15 @@ mod tests {
     }
 
     #[test]
-    fn test_part_2_sample() {
-        let solver = Day09 {};
-        let result = solver.run_part_2(PathBuf::from("tests/day_09/sample.txt"));
-        assert_eq!(result, Outcome::U64(24));
+    fn parallel_same_as_sequential() {
+        let input = fs::read_to_string("tests/day_09/sample.txt").unwrap();
+        let points = Day09::build_points(&input).unwrap();
+        let (lines, lines_vertical) = Day09::perimeter(&points).unwrap();
+        for execution in [Execution::Sequential, Execution::Parallel] {
+            assert_eq!(
+                Day09::biggest_inside(&points, &lines, &lines_vertical, execution).unwrap(),
+                24
+            );
+        }
     }
 }
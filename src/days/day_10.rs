@@ -1,22 +1,25 @@
-use crate::shared::{Outcome, Solution};
+use crate::days::DayInfo;
+use crate::shared::{
+    CheckedIterator, Execution, Outcome, Solution, SolveError, SolveResult, Trace,
+};
 use std::collections::HashSet;
-use std::io::BufRead;
-use std::path::PathBuf;
 
 type Lights = Vec<bool>;
 type Button = Vec<usize>;
 type Joltages = Vec<usize>;
 
 /// Machine abstraction (one input line)
-struct Machine {
+pub struct Machine {
     lights: Lights,
     buttons: Vec<Button>,
     joltages: Joltages,
 }
 
-impl From<&str> for Machine {
+impl TryFrom<&str> for Machine {
+    type Error = SolveError;
+
     /// Constructor from a line
-    fn from(line: &str) -> Self {
+    fn try_from(line: &str) -> Result<Self, Self::Error> {
         let mut lights = Vec::new();
         let mut buttons = Vec::new();
         let mut joltages = Vec::new();
@@ -24,6 +27,7 @@ impl From<&str> for Machine {
         for block in line.split_whitespace() {
             let mut block_chars = block.chars();
             match block_chars.next().unwrap() {
+                // `split_whitespace` never gives empty blocks
                 '[' => {
                     lights = block
                         .chars()
@@ -34,23 +38,30 @@ impl From<&str> for Machine {
                         })
                         .collect();
                 }
-                '(' => buttons.push(Machine::line_to_array(block)),
-                '{' => joltages = Machine::line_to_array(block),
-                _ => panic!("Unrecognized field"),
+                '(' => buttons.push(Machine::line_to_array(block)?),
+                '{' => joltages = Machine::line_to_array(block)?,
+                _ => return Err(SolveError::parse(format!("Unrecognized field `{block}`"))),
             }
         }
 
-        Self {
+        let size = lights.len();
+        if joltages.len() != size || buttons.iter().flatten().any(|&idx| idx >= size) {
+            return Err(SolveError::parse(
+                "Buttons and joltages don't match the number of lights",
+            ));
+        }
+
+        Ok(Self {
             lights,
             buttons,
             joltages,
-        }
+        })
     }
 }
 
 impl Machine {
     /// Helper to contains a string like {3, 5, 4} into a vector
-    fn line_to_array(block: &str) -> Vec<usize> {
+    fn line_to_array(block: &str) -> Result<Vec<usize>, SolveError> {
         let mut block_chars = block.chars();
         block_chars.next();
         block_chars.next_back();
@@ -58,7 +69,11 @@ impl Machine {
         block_chars
             .as_str()
             .split(',')
-            .map(|c| c.parse().unwrap())
+            .map(|c| {
+                c.parse().map_err(|_| {
+                    SolveError::parse(format!("Expected a list of numbers in `{block}`"))
+                })
+            })
             .collect()
     }
 
@@ -81,13 +96,18 @@ impl Machine {
     }
 
     /// Return minimal number of button presses to find the lights state
-    pub fn minimum_button_presses_lights(&self) -> u64 {
+    ///
+    /// # Errors
+    ///
+    /// If no combination of buttons gives the lights state.
+    pub fn minimum_button_presses_lights(&self) -> Result<u64, SolveError> {
         // Cleverly brute force all button options
         // One big optimization is to merge button-paths with identical outcomes
         // We don't need the actual button sequence, so just track a set of light-states, together
         // with a count of the total number of button presses.
 
         let mut states: HashSet<Lights> = HashSet::from([vec![false; self.lights.len()]]);
+        let mut seen = states.clone();
         let mut count = 0;
         loop {
             let mut new_states: HashSet<Lights> = HashSet::new();
@@ -99,14 +119,26 @@ impl Machine {
                 }
             }
             if new_states.contains(&self.lights) {
-                return count;
+                return Ok(count);
+            }
+            // States that were found before only lead to states that were found before too
+            new_states.retain(|state| !seen.contains(state));
+            if new_states.is_empty() {
+                return Err(SolveError::Unsolvable(String::from(
+                    "No buttons give the lights state",
+                )));
             }
+            seen.extend(new_states.iter().cloned());
             states = new_states;
         }
     }
 
     /// Return minimal number of button presses to find the joltages state
-    pub fn minimum_button_presses_joltages(&self) -> u64 {
+    ///
+    /// # Errors
+    ///
+    /// If no combination of buttons gives the joltages.
+    pub fn minimum_button_presses_joltages(&self) -> Result<u64, SolveError> {
         // Similar to the other method
 
         let mut states: HashSet<Joltages> = HashSet::from([vec![0; self.joltages.len()]]);
@@ -115,22 +147,26 @@ impl Machine {
             let mut new_states: HashSet<Joltages> = HashSet::new();
             count += 1;
             for state in states.drain() {
-                if state
-                    .iter()
-                    .enumerate()
-                    .any(|(idx, &val)| val > self.joltages[idx])
-                {
-                    continue;
-                    // Any of the joltage number has surpassed the target, this branch is useless
-                }
-
                 for button in &self.buttons {
                     let new_state = Self::add_joltages(&state, button);
+                    if new_state
+                        .iter()
+                        .zip(&self.joltages)
+                        .any(|(val, target)| val > target)
+                    {
+                        continue;
+                        // Any of the joltage number has surpassed the target, this branch is useless
+                    }
                     new_states.insert(new_state);
                 }
             }
             if new_states.contains(&self.joltages) {
-                return count;
+                return Ok(count);
+            }
+            if new_states.is_empty() {
+                return Err(SolveError::Unsolvable(String::from(
+                    "No buttons give the joltages",
+                )));
             }
             states = new_states;
         }
@@ -139,35 +175,61 @@ impl Machine {
 
 pub struct Day10;
 
-impl Solution for Day10 {
-    fn run_part_1(&self, input_file: PathBuf) -> Outcome {
-        let machines = self.make_machines(input_file);
+/// Registration of the solver for day 10
+pub const INFO: DayInfo = DayInfo {
+    day: 10,
+    title: "Factory",
+    implemented: true,
+    params: &[],
+    solver: |_| Box::new(Day10),
+};
 
-        let result: u64 = machines
-            .iter()
-            .map(Machine::minimum_button_presses_lights)
-            .sum();
+impl Solution for Day10 {
+    type Parsed = Vec<Machine>;
 
-        Outcome::U64(result)
+    fn parse(&self, input: &str, _trace: &Trace) -> Result<Self::Parsed, SolveError> {
+        Self::make_machines(input)
     }
 
-    fn run_part_2(&self, input_file: PathBuf) -> Outcome {
-        let machines = self.make_machines(input_file);
-
-        let result: u64 = machines
-            .iter()
-            .map(Machine::minimum_button_presses_joltages)
-            .sum();
+    fn part_1(&self, machines: &Self::Parsed, _trace: &Trace) -> SolveResult {
+        Self::total_presses(
+            machines,
+            Machine::minimum_button_presses_lights,
+            Execution::DEFAULT,
+        )
+    }
 
-        Outcome::U64(result)
+    fn part_2(&self, machines: &Self::Parsed, _trace: &Trace) -> SolveResult {
+        Self::total_presses(
+            machines,
+            Machine::minimum_button_presses_joltages,
+            Execution::DEFAULT,
+        )
     }
 }
 
 impl Day10 {
-    fn make_machines(&self, input_file: PathBuf) -> Vec<Machine> {
-        self.get_file_reader(input_file)
+    /// Solve each machine independently and add up the button presses
+    fn total_presses(
+        machines: &[Machine],
+        presses: fn(&Machine) -> Result<u64, SolveError>,
+        execution: Execution,
+    ) -> SolveResult {
+        let result: u64 = execution
+            .map(machines, presses)
+            .into_iter()
+            .collect::<Result<Vec<u64>, SolveError>>()?
+            .into_iter()
+            .checked_sum("Total button presses")?;
+
+        Ok(Outcome::U64(result))
+    }
+
+    fn make_machines(input: &str) -> Result<Vec<Machine>, SolveError> {
+        input
             .lines()
-            .map(|line| Machine::from(line.unwrap().as_str()))
+            .enumerate()
+            .map(|(idx, line)| Machine::try_from(line).map_err(|err| err.on_line(idx + 1)))
             .collect()
     }
 }
@@ -175,18 +237,57 @@ impl Day10 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::fs;
 
     #[test]
-    fn part_1_sample() {
-        let solver = Day10 {};
-        let result = solver.run_part_1(PathBuf::from("tests/day_10/sample.txt"));
-        assert_eq!(result, Outcome::U64(7));
+    fn parallel_same_as_sequential() {
+        let input = fs::read_to_string("tests/day_10/sample.txt").unwrap();
+        let machines = Day10::make_machines(&input).unwrap();
+        for presses in [
+            Machine::minimum_button_presses_lights,
+            Machine::minimum_button_presses_joltages,
+        ] {
+            assert_eq!(
+                Day10::total_presses(&machines, presses, Execution::Parallel).unwrap(),
+                Day10::total_presses(&machines, presses, Execution::Sequential).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn machine_unsolvable() {
+        let machine = Machine::try_from("[.##.] {3,5,4,7}").unwrap();
+        assert!(matches!(
+            machine.minimum_button_presses_lights(),
+            Err(SolveError::Unsolvable(_))
+        ));
+        assert!(matches!(
+            machine.minimum_button_presses_joltages(),
+            Err(SolveError::Unsolvable(_))
+        ));
+
+        // No button toggles the first light
+        let machine = Machine::try_from("[#...] (1,2) (3) {1,1,1,1}").unwrap();
+        assert!(machine.minimum_button_presses_lights().is_err());
+        assert!(machine.minimum_button_presses_joltages().is_err());
+        let machines = vec![machine];
+        let err = Day10::total_presses(
+            &machines,
+            Machine::minimum_button_presses_lights,
+            Execution::Sequential,
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "No solution found: No buttons give the lights state"
+        );
     }
 
     #[test]
-    fn part_2_sample() {
-        let solver = Day10 {};
-        let result = solver.run_part_2(PathBuf::from("tests/day_10/sample.txt"));
-        assert_eq!(result, Outcome::U64(33));
+    fn machine_invalid() {
+        assert!(Machine::try_from("[.##.] (3) (1,3) {3,5,4,7}").is_ok());
+        assert!(Machine::try_from("[.##.] (3) (1,x) {3,5,4,7}").is_err());
+        assert!(Machine::try_from("[.##.] (3) (1,4) {3,5,4,7}").is_err());
+        assert!(Machine::try_from("[.##.] (3) <1> {3,5,4,7}").is_err());
     }
 }
@@ -1,24 +1,41 @@
-use crate::shared::{Outcome, Solution};
-use std::path::PathBuf;
+use crate::days::DayInfo;
+use crate::shared::{Outcome, Solution, SolveError, SolveResult, Trace};
 
 pub struct Day12;
 
+/// Registration of the solver for day 12
+pub const INFO: DayInfo = DayInfo {
+    day: 12,
+    title: "Christmas Tree Farm",
+    implemented: false,
+    params: &[],
+    solver: |_| Box::new(Day12),
+};
+
 impl Solution for Day12 {
-    fn run_part_1(&self, _input_file: PathBuf) -> Outcome {
-        Outcome::Text(String::from("d12p1"))
+    type Parsed = Vec<String>;
+
+    fn parse(&self, input: &str, _trace: &Trace) -> Result<Self::Parsed, SolveError> {
+        Ok(input.lines().map(String::from).collect())
+    }
+
+    fn part_1(&self, _lines: &Self::Parsed, _trace: &Trace) -> SolveResult {
+        Ok(Outcome::Text(String::from("d12p1")))
     }
 
-    fn run_part_2(&self, _input_file: PathBuf) -> Outcome {
-        Outcome::Text(String::from("d12p2"))
+    fn part_2(&self, _lines: &Self::Parsed, _trace: &Trace) -> SolveResult {
+        Ok(Outcome::Text(String::from("d12p2")))
     }
 }
 
 #[cfg(test)]
 mod tests {
-    // use super::*;
+    use super::*;
+    use std::fs;
 
     #[test]
-    fn test() {
-        assert_eq!(1 + 1, 2);
+    fn parse_sample() {
+        let input = fs::read_to_string("tests/day_12/sample.txt").unwrap();
+        assert!(Day12.parse(&input, &Trace::disabled()).is_ok());
     }
 }
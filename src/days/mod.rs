@@ -1,3 +1,29 @@
+//! Solvers for each of the days
+
+use crate::shared::{ParamSpec, Params, Solver};
+
+/// Registration of a daily solver, declared next to the solver itself
+#[derive(Debug, Clone, Copy)]
+pub struct DayInfo {
+    pub day: u8,
+    pub title: &'static str,
+    pub implemented: bool, // False for placeholders that are not solved yet
+    pub params: &'static [ParamSpec],
+    pub solver: fn(&Params) -> Box<dyn Solver>, // Create the solver with resolved parameters
+}
+
+impl DayInfo {
+    /// Create the solver, overriding default parameters with assignments like `limit=10`
+    ///
+    /// # Errors
+    ///
+    /// If an assignment is invalid for the parameters of this day.
+    pub fn create(&self, assignments: &[String]) -> Result<Box<dyn Solver>, String> {
+        let params = Params::resolve(self.params, assignments)?;
+        Ok((self.solver)(&params))
+    }
+}
+
 mod day_01;
 mod day_02;
 mod day_03;
@@ -11,36 +37,65 @@ mod day_10;
 mod day_11;
 mod day_12;
 
-pub use day_01::Day01;
-pub use day_02::Day02;
-pub use day_03::Day03;
-pub use day_04::Day04;
-pub use day_05::Day05;
-pub use day_06::Day06;
-pub use day_07::Day07;
-pub use day_08::Day08;
-pub use day_09::Day09;
-pub use day_10::Day10;
-pub use day_11::Day11;
-pub use day_12::Day12;
-
-use crate::shared::Solution;
-
-/// Return an instance of a solver based on the day number
-pub fn get_solver(number: u8) -> Box<dyn Solution> {
-    match number {
-        1 => Box::new(Day01 {}),
-        2 => Box::new(Day02 {}),
-        3 => Box::new(Day03 {}),
-        4 => Box::new(Day04 {}),
-        5 => Box::new(Day05 {}),
-        6 => Box::new(Day06 {}),
-        7 => Box::new(Day07 {}),
-        8 => Box::new(Day08 { limit: 1_000 }),
-        9 => Box::new(Day09 {}),
-        10 => Box::new(Day10 {}),
-        11 => Box::new(Day11 {}),
-        12 => Box::new(Day12 {}),
-        _ => panic!("Invalid number for <day>"), // Also covered by CLI validator
+/// Export the solvers of the day modules and collect their registrations into [`DAYS`]
+macro_rules! register_days {
+    ($($module:ident => $solver:ident),* $(,)?) => {
+        $(
+            pub use $module::$solver;
+        )*
+
+        /// Registrations of all days, in order
+        pub const DAYS: &[DayInfo] = &[$($module::INFO),*];
+    };
+}
+
+register_days! {
+    day_01 => Day01,
+    day_02 => Day02,
+    day_03 => Day03,
+    day_04 => Day04,
+    day_05 => Day05,
+    day_06 => Day06,
+    day_07 => Day07,
+    day_08 => Day08,
+    day_09 => Day09,
+    day_10 => Day10,
+    day_11 => Day11,
+    day_12 => Day12,
+}
+
+/// Find the registration of a day, if there is any
+#[must_use]
+pub fn find_day(number: u8) -> Option<&'static DayInfo> {
+    DAYS.iter().find(|info| info.day == number)
+}
+
+/// List the numbers of all registered days
+pub fn day_numbers() -> impl Iterator<Item = u8> {
+    DAYS.iter().map(|info| info.day)
+}
+
+/// Return an instance of a solver based on the day number, with default parameters
+///
+/// # Panics
+///
+/// If there is no solver for this day.
+#[must_use]
+pub fn get_solver(number: u8) -> Box<dyn Solver> {
+    let info = find_day(number).expect("Invalid number for <day>"); // Also covered by CLI validator
+    (info.solver)(&Params::defaults(info.params))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registry_in_order() {
+        let numbers: Vec<u8> = day_numbers().collect();
+        assert_eq!(numbers, (1..=12).collect::<Vec<u8>>());
+        assert_eq!(find_day(8).unwrap().title, "Playground");
+        assert!(!find_day(11).unwrap().implemented);
+        assert!(find_day(13).is_none());
     }
 }
@@ -0,0 +1,243 @@
+//! Local cache of puzzle inputs, filled by downloading missing ones
+//!
+//! Downloads need a session token in `AOC_SESSION` (the `session` cookie of the website). The
+//! server can be changed with `AOC_BASE_URL`, e.g. to a local stand-in for testing.
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Environment variable holding the session token
+pub const SESSION_VAR: &str = "AOC_SESSION";
+
+/// Environment variable to override [`DEFAULT_BASE_URL`]
+pub const BASE_URL_VAR: &str = "AOC_BASE_URL";
+
+/// Server the inputs are downloaded from
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// Default location of the cached inputs
+pub const DEFAULT_CACHE_DIR: &str = "./inputs";
+
+/// Base URL and session token from the environment, `None` without a token
+pub(crate) fn server_from_env() -> Option<(String, String)> {
+    let session = env::var(SESSION_VAR)
+        .ok()
+        .filter(|s| !s.trim().is_empty())?;
+    let base_url = env::var(BASE_URL_VAR).unwrap_or_else(|_| DEFAULT_BASE_URL.to_string());
+    Some((base_url, session.trim().to_string()))
+}
+
+/// Identification sent along with every request
+pub(crate) const USER_AGENT: &str = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"));
+
+/// Source of puzzle inputs that are not cached yet
+pub trait Fetcher {
+    /// Get the input of a day
+    ///
+    /// # Errors
+    ///
+    /// If the input could not be retrieved.
+    fn fetch(&self, day: u8) -> Result<String, String>;
+}
+
+/// Download inputs over HTTP, leaving at least `interval` between requests
+pub struct HttpFetcher {
+    base_url: String,
+    session: String,
+    interval: Duration,
+    last_request: Mutex<Option<Instant>>,
+}
+
+impl HttpFetcher {
+    /// Minimal time between two requests, to go easy on the server
+    pub const DEFAULT_INTERVAL: Duration = Duration::from_secs(3);
+
+    /// Download from `base_url`, sending the session token as cookie
+    #[must_use]
+    pub fn new(base_url: &str, session: &str) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.to_string(),
+            interval: Self::DEFAULT_INTERVAL,
+            last_request: Mutex::new(None),
+        }
+    }
+
+    /// Take the session token and base URL from the environment, `None` without a token
+    #[must_use]
+    pub fn from_env() -> Option<Self> {
+        let (base_url, session) = server_from_env()?;
+        Some(Self::new(&base_url, &session))
+    }
+
+    /// Change the minimal time between two requests
+    #[must_use]
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Location of the input of a day
+    #[must_use]
+    pub fn url(&self, day: u8) -> String {
+        format!("{}/2025/day/{day}/input", self.base_url)
+    }
+
+    /// Sleep until the interval since the previous request has passed
+    fn wait_for_turn(&self) {
+        let mut last_request = self
+            .last_request
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        if let Some(last) = *last_request {
+            thread::sleep(self.interval.saturating_sub(last.elapsed()));
+        }
+        *last_request = Some(Instant::now());
+    }
+}
+
+impl Fetcher for HttpFetcher {
+    fn fetch(&self, day: u8) -> Result<String, String> {
+        self.wait_for_turn();
+        let url = self.url(day);
+        ureq::get(&url)
+            .header("Cookie", &format!("session={}", self.session))
+            .header("User-Agent", USER_AGENT)
+            .call()
+            .and_then(|mut response| response.body_mut().read_to_string())
+            .map_err(|err| format!("Failed to download {url}: {err}"))
+    }
+}
+
+/// Inputs stored as `day_<nn>.txt` in a directory, fetching the missing ones
+pub struct InputCache {
+    dir: PathBuf,
+    fetcher: Option<Box<dyn Fetcher>>, // Without one, only cached inputs are available
+}
+
+impl InputCache {
+    /// Cache in `dir`, fetching missing inputs with `fetcher` if given
+    #[must_use]
+    pub fn new(dir: &Path, fetcher: Option<Box<dyn Fetcher>>) -> Self {
+        Self {
+            dir: dir.to_path_buf(),
+            fetcher,
+        }
+    }
+
+    /// Use the default directory, downloading when a session token is set
+    #[must_use]
+    pub fn from_env() -> Self {
+        let fetcher = HttpFetcher::from_env().map(|f| Box::new(f) as Box<dyn Fetcher>);
+        Self::new(Path::new(DEFAULT_CACHE_DIR), fetcher)
+    }
+
+    /// Location of the cached input of a day
+    #[must_use]
+    pub fn path(&self, day: u8) -> PathBuf {
+        self.dir.join(format!("day_{day:02}.txt"))
+    }
+
+    /// Make sure the input of a day is cached, returns its path
+    ///
+    /// # Errors
+    ///
+    /// If the input is missing and cannot be fetched or stored.
+    pub fn ensure(&self, day: u8) -> Result<PathBuf, String> {
+        let path = self.path(day);
+        if path.exists() {
+            return Ok(path);
+        }
+        let Some(fetcher) = &self.fetcher else {
+            return Err(format!(
+                "No input in {} and no {SESSION_VAR} set to download it",
+                path.display()
+            ));
+        };
+
+        let input = fetcher.fetch(day)?;
+        fs::create_dir_all(&self.dir)
+            .map_err(|err| format!("Failed to create {}: {err}", self.dir.display()))?;
+        // Write next to it first, such that an interrupted write leaves no partial input
+        let partial = path.with_extension("part");
+        fs::write(&partial, input)
+            .and_then(|()| fs::rename(&partial, &path))
+            .map_err(|err| format!("Failed to write {}: {err}", path.display()))?;
+        Ok(path)
+    }
+
+    /// Fetch the missing inputs of several days, giving the errors
+    #[must_use]
+    pub fn ensure_all(&self, days: &[u8]) -> Vec<String> {
+        days.iter()
+            .filter_map(|&day| self.ensure(day).err())
+            .collect()
+    }
+
+    /// Whether missing inputs can be fetched at all
+    #[must_use]
+    pub fn can_fetch(&self) -> bool {
+        self.fetcher.is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    /// Fetcher that makes up inputs and counts the requests
+    struct FakeFetcher {
+        requests: Rc<Cell<u32>>,
+    }
+
+    impl Fetcher for FakeFetcher {
+        fn fetch(&self, day: u8) -> Result<String, String> {
+            self.requests.set(self.requests.get() + 1);
+            if day == 13 {
+                return Err(String::from("Not found"));
+            }
+            Ok(format!("input {day}\n"))
+        }
+    }
+
+    #[test]
+    fn cache_fetches_once() {
+        let dir = env::temp_dir().join(format!("aoc_cache_{}", std::process::id()));
+        let requests = Rc::new(Cell::new(0));
+        let fetcher = FakeFetcher {
+            requests: requests.clone(),
+        };
+        let cache = InputCache::new(&dir, Some(Box::new(fetcher)));
+
+        let path = cache.ensure(3).unwrap();
+        assert_eq!(path, dir.join("day_03.txt"));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "input 3\n");
+        assert_eq!(cache.ensure(3).unwrap(), path);
+        assert_eq!(requests.get(), 1);
+
+        assert_eq!(
+            cache.ensure_all(&[3, 4, 13]),
+            vec![String::from("Not found")]
+        );
+        assert_eq!(requests.get(), 3);
+        assert!(!dir.join("day_13.txt").exists());
+
+        let offline = InputCache::new(&dir, None);
+        assert!(offline.ensure(4).is_ok());
+        assert!(offline.ensure(5).unwrap_err().contains(SESSION_VAR));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn fetcher_url() {
+        let fetcher = HttpFetcher::new("http://127.0.0.1:8000/", "abc");
+        assert_eq!(fetcher.url(7), "http://127.0.0.1:8000/2025/day/7/input");
+    }
+}
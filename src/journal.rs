@@ -0,0 +1,408 @@
+//! History of submitted answers, to avoid submitting an answer that is known to be wrong
+//!
+//! The journal is a TOML file with the attempts per day and part, oldest first:
+//!
+//! ```toml
+//! [[day_01.part_1]]
+//! answer = "1234"
+//! verdict = "too-high"
+//! ```
+
+use crate::answers::number_from_key;
+use crate::fetch::{self, USER_AGENT};
+use crate::shared::Outcome;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::path::Path;
+
+/// Default location of the journal
+pub const DEFAULT_JOURNAL_FILE: &str = "./inputs/journal.toml";
+
+/// Response to a submitted answer
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum Verdict {
+    Right,
+    TooHigh,
+    TooLow,
+    Wrong, // Wrong without a hint in which direction
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            Verdict::Right => write!(f, "right"),
+            Verdict::TooHigh => write!(f, "too high"),
+            Verdict::TooLow => write!(f, "too low"),
+            Verdict::Wrong => write!(f, "wrong"),
+        }
+    }
+}
+
+/// A single submitted answer
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Attempt {
+    pub answer: String,
+    pub verdict: Verdict,
+}
+
+impl Attempt {
+    /// The answer in the form the solvers give it
+    #[must_use]
+    pub fn outcome(&self) -> Outcome {
+        Outcome::from_text(&self.answer)
+    }
+}
+
+/// What the journal knows about a new answer
+#[derive(Debug, PartialEq)]
+pub enum Check {
+    Unknown,          // Not submitted before, and within the known bounds
+    Right,            // This answer was accepted before
+    Solved(Outcome),  // Another answer was accepted before
+    Known(Verdict),   // This answer was rejected before
+    TooLow(Outcome),  // At or below this answer, which was too low
+    TooHigh(Outcome), // At or above this answer, which was too high
+}
+
+impl Check {
+    /// Whether the answer is certainly wrong
+    #[must_use]
+    pub fn is_wrong(&self) -> bool {
+        !matches!(self, Check::Unknown | Check::Right)
+    }
+}
+
+impl Display for Check {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            Check::Unknown => write!(f, "not submitted before"),
+            Check::Right => write!(f, "already accepted"),
+            Check::Solved(answer) => write!(f, "already solved with {answer}"),
+            Check::Known(verdict) => write!(f, "already submitted, it was {verdict}"),
+            Check::TooLow(low) => write!(f, "{low} was too low already"),
+            Check::TooHigh(high) => write!(f, "{high} was too high already"),
+        }
+    }
+}
+
+/// Submitted answers, keyed by day and part
+#[derive(Debug, Default, PartialEq)]
+pub struct Journal {
+    attempts: BTreeMap<(u8, u8), Vec<Attempt>>,
+}
+
+/// Layout of the file, like `day_01.part_1 = [...]`
+type JournalFile = BTreeMap<String, BTreeMap<String, Vec<Attempt>>>;
+
+impl Journal {
+    /// Load the journal from a file, a missing file gives an empty journal
+    ///
+    /// # Errors
+    ///
+    /// If the file cannot be read or parsed.
+    pub fn from_file(path: &Path) -> Result<Self, String> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(path)
+            .map_err(|err| format!("Failed to read {}: {err}", path.display()))?;
+        Self::parse(&content).map_err(|err| format!("In {}: {err}", path.display()))
+    }
+
+    /// Parse the content of a journal file
+    ///
+    /// # Errors
+    ///
+    /// If the content is not valid TOML or does not follow the journal layout.
+    pub fn parse(content: &str) -> Result<Self, String> {
+        let file: JournalFile = toml::from_str(content).map_err(|err| format!("{err}"))?;
+        let mut attempts = BTreeMap::new();
+        for (day_key, parts) in file {
+            let day = number_from_key(&day_key, "day_")?;
+            for (part_key, list) in parts {
+                let part = number_from_key(&part_key, "part_")?;
+                attempts.insert((day, part), list);
+            }
+        }
+        Ok(Self { attempts })
+    }
+
+    /// Write the journal to a file
+    ///
+    /// # Errors
+    ///
+    /// If the file cannot be written.
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let mut file = JournalFile::new();
+        for (&(day, part), list) in &self.attempts {
+            file.entry(format!("day_{day:02}"))
+                .or_default()
+                .insert(format!("part_{part}"), list.clone());
+        }
+        let content = toml::to_string(&file).map_err(|err| format!("{err}"))?;
+        fs::write(path, content).map_err(|err| format!("Failed to write {}: {err}", path.display()))
+    }
+
+    /// Submitted answers of a day and part, oldest first
+    #[must_use]
+    pub fn attempts(&self, day: u8, part: u8) -> &[Attempt] {
+        self.attempts.get(&(day, part)).map_or(&[], Vec::as_slice)
+    }
+
+    /// Add a submitted answer
+    pub fn record(&mut self, day: u8, part: u8, answer: &Outcome, verdict: Verdict) {
+        self.attempts.entry((day, part)).or_default().push(Attempt {
+            answer: answer.to_string(),
+            verdict,
+        });
+    }
+
+    /// Compare an answer against the earlier attempts
+    #[must_use]
+    pub fn check(&self, day: u8, part: u8, answer: &Outcome) -> Check {
+        let attempts = self.attempts(day, part);
+        if let Some(right) = attempts.iter().find(|a| a.verdict == Verdict::Right) {
+            let right = right.outcome();
+            return if right == *answer {
+                Check::Right
+            } else {
+                Check::Solved(right)
+            };
+        }
+        if let Some(known) = attempts.iter().find(|a| a.outcome() == *answer) {
+            return Check::Known(known.verdict);
+        }
+
+        // Tightest bounds from the hints, numbers only
+        let bound = |verdict| {
+            attempts
+                .iter()
+                .filter(move |a| a.verdict == verdict)
+                .map(Attempt::outcome)
+        };
+        if let Some(high) = bound(Verdict::TooHigh)
+            .filter(|high| answer >= high)
+            .reduce(|a, b| if b < a { b } else { a })
+        {
+            return Check::TooHigh(high);
+        }
+        if let Some(low) = bound(Verdict::TooLow)
+            .filter(|low| answer <= low)
+            .reduce(|a, b| if b > a { b } else { a })
+        {
+            return Check::TooLow(low);
+        }
+        Check::Unknown
+    }
+}
+
+/// Endpoint that judges answers
+pub trait Submitter {
+    /// Submit an answer for a day and part
+    ///
+    /// # Errors
+    ///
+    /// If the answer could not be submitted or the response is not understood.
+    fn submit(&self, day: u8, part: u8, answer: &str) -> Result<Verdict, String>;
+}
+
+/// Submit answers to the puzzle website, using the same settings as [`fetch::HttpFetcher`]
+pub struct HttpSubmitter {
+    base_url: String,
+    session: String,
+}
+
+impl HttpSubmitter {
+    /// Submit to `base_url`, sending the session token as cookie
+    #[must_use]
+    pub fn new(base_url: &str, session: &str) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.to_string(),
+        }
+    }
+
+    /// Take the session token and base URL from the environment, `None` without a token
+    #[must_use]
+    pub fn from_env() -> Option<Self> {
+        let (base_url, session) = fetch::server_from_env()?;
+        Some(Self::new(&base_url, &session))
+    }
+}
+
+impl Submitter for HttpSubmitter {
+    fn submit(&self, day: u8, part: u8, answer: &str) -> Result<Verdict, String> {
+        let url = format!("{}/2025/day/{day}/answer", self.base_url);
+        let page = ureq::post(&url)
+            .header("Cookie", &format!("session={}", self.session))
+            .header("User-Agent", USER_AGENT)
+            .send_form([("level", part.to_string().as_str()), ("answer", answer)])
+            .and_then(|mut response| response.body_mut().read_to_string())
+            .map_err(|err| format!("Failed to submit to {url}: {err}"))?;
+        verdict_from_page(&page)
+    }
+}
+
+/// Read the verdict from the page that answers a submission
+fn verdict_from_page(page: &str) -> Result<Verdict, String> {
+    if page.contains("That's the right answer") {
+        Ok(Verdict::Right)
+    } else if page.contains("your answer is too high") {
+        Ok(Verdict::TooHigh)
+    } else if page.contains("your answer is too low") {
+        Ok(Verdict::TooLow)
+    } else if page.contains("That's not the right answer") {
+        Ok(Verdict::Wrong)
+    } else if page.contains("You gave an answer too recently") {
+        Err(String::from(
+            "Answered too recently, wait a bit before trying again",
+        ))
+    } else if page.contains("You don't seem to be solving the right level") {
+        Err(String::from("This part is locked or already solved"))
+    } else {
+        Err(String::from("Unexpected response to the submission"))
+    }
+}
+
+/// Submit an answer unless the journal knows it is wrong, and record the verdict
+///
+/// With `force`, an answer that is known to be wrong is submitted anyway. An answer that was
+/// accepted before is not submitted again.
+///
+/// # Errors
+///
+/// If the answer is known to be wrong, is a grid or the submission fails.
+pub fn submit(
+    journal: &mut Journal,
+    submitter: &dyn Submitter,
+    (day, part): (u8, u8),
+    answer: &Outcome,
+    force: bool,
+) -> Result<Verdict, String> {
+    match journal.check(day, part, answer) {
+        Check::Right => return Ok(Verdict::Right),
+        check if check.is_wrong() && !force => {
+            return Err(format!("Not submitting {answer}: {check}"));
+        }
+        _ => {}
+    }
+    if let Outcome::Grid(_) = answer {
+        return Err(String::from(
+            "A grid cannot be submitted, read the letters from it",
+        ));
+    }
+
+    let verdict = submitter.submit(day, part, &answer.to_string())?;
+    journal.record(day, part, answer, verdict);
+    Ok(verdict)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+
+    /// Submitter with a fixed right answer, remembering what was submitted
+    struct StubSubmitter {
+        right: u64,
+        submitted: RefCell<Vec<String>>,
+    }
+
+    impl Submitter for StubSubmitter {
+        fn submit(&self, _day: u8, _part: u8, answer: &str) -> Result<Verdict, String> {
+            self.submitted.borrow_mut().push(answer.to_string());
+            let answer: u64 = answer.parse().map_err(|_| "Not a number")?;
+            Ok(match answer.cmp(&self.right) {
+                std::cmp::Ordering::Less => Verdict::TooLow,
+                std::cmp::Ordering::Equal => Verdict::Right,
+                std::cmp::Ordering::Greater => Verdict::TooHigh,
+            })
+        }
+    }
+
+    #[test]
+    fn check_against_attempts() {
+        let mut journal = Journal::default();
+        journal.record(1, 1, &Outcome::U64(100), Verdict::TooHigh);
+        journal.record(1, 1, &Outcome::U64(80), Verdict::TooHigh);
+        journal.record(1, 1, &Outcome::U64(20), Verdict::TooLow);
+        journal.record(1, 1, &Outcome::U64(50), Verdict::Wrong);
+
+        let check = |journal: &Journal, n| journal.check(1, 1, &Outcome::U64(n));
+        assert_eq!(check(&journal, 60), Check::Unknown);
+        assert_eq!(check(&journal, 50), Check::Known(Verdict::Wrong));
+        assert_eq!(check(&journal, 90), Check::TooHigh(Outcome::U64(80)));
+        assert_eq!(check(&journal, 20), Check::Known(Verdict::TooLow));
+        assert_eq!(check(&journal, 10), Check::TooLow(Outcome::U64(20)));
+        assert_eq!(journal.check(1, 2, &Outcome::U64(90)), Check::Unknown);
+
+        journal.record(1, 1, &Outcome::U64(60), Verdict::Right);
+        assert_eq!(check(&journal, 60), Check::Right);
+        assert_eq!(check(&journal, 61), Check::Solved(Outcome::U64(60)));
+    }
+
+    #[test]
+    fn journal_round_trip() {
+        let mut journal = Journal::default();
+        journal.record(3, 2, &Outcome::I64(-4), Verdict::TooLow);
+        journal.record(3, 2, &Outcome::Text(String::from("ABC")), Verdict::Right);
+        journal.record(12, 1, &Outcome::U128(1 << 70), Verdict::Wrong);
+
+        let path = std::env::temp_dir().join(format!("aoc_journal_{}.toml", std::process::id()));
+        journal.save(&path).unwrap();
+        let content = fs::read_to_string(&path).unwrap();
+        assert!(content.contains("[[day_03.part_2]]\nanswer = \"-4\"\nverdict = \"too-low\"\n"));
+        assert_eq!(Journal::from_file(&path).unwrap(), journal);
+        fs::remove_file(&path).unwrap();
+
+        assert!(
+            Journal::parse("[[day_01.part_1]]\nanswer = \"1\"\nverdict = \"maybe\"\n").is_err()
+        );
+    }
+
+    #[test]
+    fn submit_with_guard() {
+        let submitter = StubSubmitter {
+            right: 42,
+            submitted: RefCell::new(Vec::new()),
+        };
+        let mut journal = Journal::default();
+        let mut submit_n =
+            |n, force| submit(&mut journal, &submitter, (5, 1), &Outcome::U64(n), force);
+
+        assert_eq!(submit_n(50, false), Ok(Verdict::TooHigh));
+        assert_eq!(
+            submit_n(60, false),
+            Err(String::from("Not submitting 60: 50 was too high already"))
+        );
+        assert_eq!(submit_n(60, true), Ok(Verdict::TooHigh));
+        assert_eq!(submit_n(42, false), Ok(Verdict::Right));
+        assert_eq!(submit_n(42, false), Ok(Verdict::Right));
+        assert_eq!(*submitter.submitted.borrow(), vec!["50", "60", "42"]);
+    }
+
+    #[test]
+    fn verdict_from_response() {
+        let page = |text| format!("<main><article><p>{text}</p></article></main>");
+        assert_eq!(
+            verdict_from_page(&page(
+                "That's the right answer! You are one gold star closer."
+            )),
+            Ok(Verdict::Right)
+        );
+        assert_eq!(
+            verdict_from_page(&page(
+                "That's not the right answer; your answer is too low."
+            )),
+            Ok(Verdict::TooLow)
+        );
+        assert_eq!(
+            verdict_from_page(&page("That's not the right answer.")),
+            Ok(Verdict::Wrong)
+        );
+        assert!(verdict_from_page(&page("You gave an answer too recently")).is_err());
+    }
+}
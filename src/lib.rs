@@ -0,0 +1,28 @@
+//! Advent of code 2025 solutions
+//!
+//! The daily solvers and the shared helpers (like [`shared::Grid`] and [`shared::RowCol`]) are
+//! available as a library, the executable is only a thin command line interface on top.
+//!
+//! ```
+//! use advent_of_code_2025::days::get_solver;
+//! use advent_of_code_2025::shared::{Outcome, Trace};
+//!
+//! let solver = get_solver(1);
+//! let result = solver.run("L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82\n", 1, &Trace::disabled());
+//! assert_eq!(result.unwrap(), Outcome::U64(3));
+//! ```
+
+#![warn(clippy::pedantic)]
+
+pub mod answers;
+pub mod bench;
+pub mod days;
+pub mod fetch;
+pub mod journal;
+pub mod memory;
+pub mod output;
+pub mod runner;
+pub mod samples;
+pub mod scaffold;
+pub mod shared;
+pub mod watch;
@@ -1,65 +1,625 @@
 #![warn(clippy::pedantic)]
 
-mod days;
-mod shared;
+use advent_of_code_2025::answers::{self, Answers, Status};
+use advent_of_code_2025::bench::{self, Baseline, BenchConfig};
+use advent_of_code_2025::days;
+use advent_of_code_2025::fetch::{InputCache, SESSION_VAR};
+use advent_of_code_2025::journal::{self, HttpSubmitter, Journal, Verdict};
+use advent_of_code_2025::memory::CountingAllocator;
+use advent_of_code_2025::output;
+use advent_of_code_2025::runner::{self, InputSource, Report};
+use advent_of_code_2025::samples::{self, SAMPLES_DIR};
+use advent_of_code_2025::scaffold;
+use advent_of_code_2025::shared::{Outcome, Trace};
+use advent_of_code_2025::watch::{self, Snapshot};
+use clap::builder::{PossibleValue, PossibleValuesParser, TypedValueParser};
+use clap::error::ErrorKind;
+use clap::{CommandFactory, Parser, Subcommand, ValueEnum};
+use std::fmt::Write;
+use std::path::{self, Path, PathBuf};
+use std::process::ExitCode;
+use std::thread;
+use std::time::{Duration, Instant};
 
-use clap::Parser;
-use days::get_solver;
-use std::{path, path::PathBuf, time::Instant};
+/// Counts allocations only while profiling, see `bench --profile-alloc`
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
 
 /// Advent of code 2025 solutions
 ///
 /// This single executable can be run for each day
 #[derive(Parser, Debug)]
 #[command(version, about, long_about = None)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     /// Day of the challenge
-    #[arg(value_parser=clap::value_parser!(u8).range(1..=12))]
-    day: u8,
+    #[arg(required = true, value_parser = day_parser())]
+    day: Option<u8>,
 
-    /// Path to the input.txt file
+    /// Path to the input.txt file, or `-` to read from stdin
     #[arg(short, long, default_value = "default")]
     input: PathBuf,
 
-    /// Whether to run part 1 or part 2
-    #[arg(short, long, default_value = "1", value_parser=clap::value_parser!(u8).range(1..=2))]
-    part: u8,
+    /// Whether to run part 1, part 2 or both
+    #[arg(short, long, default_value = "1")]
+    part: Part,
 
-    /// Enable printing of the timing statistic
+    /// Enable printing of the timing statistic (see `bench` for repeated measurements)
     #[arg(short, long, default_value = "false")]
     timing: bool,
 
-    /// Enable to print intermediate debugging info
-    #[arg(short, long, default_value = "false")]
-    debug: bool,
+    /// Print intermediate debugging info to stderr, repeat for more detail (up to `-ddd`)
+    #[arg(short, long, action = clap::ArgAction::Count)]
+    debug: u8,
+
+    /// Write the debugging info to this file instead of stderr
+    #[arg(long, requires = "debug")]
+    trace_file: Option<PathBuf>,
+
+    /// How to print the answers
+    #[arg(short, long, default_value = "text")]
+    format: Format,
+
+    /// Set a parameter of the solver, like `limit=10` (repeat for more)
+    #[arg(long = "param", value_name = "NAME=VALUE", long_help = params_help())]
+    params: Vec<String>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Run every day and print a table of the answers
+    All {
+        /// Whether to run part 1, part 2 or both
+        #[arg(short, long, default_value = "both")]
+        part: Part,
+
+        /// How to print the answers
+        #[arg(short, long, default_value = "text")]
+        format: Format,
+
+        /// Number of days and parts to solve at the same time, 0 for one per CPU core
+        #[arg(short, long, default_value = "1")]
+        jobs: usize,
+    },
+    /// List the registered days and whether they are solved
+    List,
+    /// Time solvers repeatedly and report statistics per phase
+    Bench(BenchArgs),
+    /// Run every day and compare the answers against the expected ones
+    Verify {
+        /// Whether to check part 1, part 2 or both
+        #[arg(short, long, default_value = "both")]
+        part: Part,
+
+        /// Path to the answers manifest
+        #[arg(short, long, default_value = "default")]
+        answers: PathBuf,
+    },
+    /// Check the sample inputs against the answers in their sidecar files
+    Samples {
+        /// Day to check, all days when omitted
+        #[arg(value_parser = day_parser())]
+        day: Option<u8>,
+    },
+    /// Re-run the samples and the real input of a day whenever its files change
+    Watch {
+        /// Day to watch
+        #[arg(value_parser = day_parser())]
+        day: u8,
+
+        /// Whether to run part 1, part 2 or both
+        #[arg(short, long, default_value = "both")]
+        part: Part,
+
+        /// Path to the input.txt file
+        #[arg(long, default_value = "default")]
+        input: PathBuf,
+
+        /// Time between checks for changes, in milliseconds
+        #[arg(short, long, default_value = "500")]
+        interval: u64,
+    },
+    /// Solve a part for the real input and submit the answer, unless it is known to be wrong
+    Submit(SubmitArgs),
+    /// Create the solver module, sample files and registration for a new day
+    New {
+        /// Day to create, a placeholder for it is replaced
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+
+        /// Title of the puzzle, defaults to the one already registered
+        #[arg(short, long)]
+        title: Option<String>,
+    },
+}
+
+#[derive(clap::Args, Debug)]
+struct BenchArgs {
+    /// Day to benchmark, all days when omitted
+    #[arg(value_parser = day_parser())]
+    day: Option<u8>,
+
+    /// Whether to run part 1, part 2 or both
+    #[arg(short, long, default_value = "both")]
+    part: Part,
+
+    /// Number of measured runs
+    #[arg(short, long, default_value = "10", value_parser=clap::value_parser!(u32).range(1..))]
+    runs: u32,
+
+    /// Number of runs before measuring
+    #[arg(short, long, default_value = "2")]
+    warmup: u32,
+
+    /// Save the median timings to this file, as baseline for a later run
+    #[arg(short, long)]
+    save: Option<PathBuf>,
+
+    /// Compare the median timings against this saved baseline
+    #[arg(short, long)]
+    baseline: Option<PathBuf>,
+
+    /// Slow-down (in %) compared to the baseline that counts as a regression
+    #[arg(short, long, default_value = "10")]
+    threshold: f64,
+
+    /// Also count the allocations, bytes allocated and peak heap of each phase, in one extra run
+    #[arg(long)]
+    profile_alloc: bool,
+}
+
+#[derive(clap::Args, Debug)]
+struct SubmitArgs {
+    /// Day to submit
+    #[arg(value_parser = day_parser())]
+    day: u8,
+
+    /// Part to submit
+    #[arg(short, long, default_value = "1", value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: u8,
+
+    /// Only record the verdict of an answer submitted elsewhere, like in the browser
+    #[arg(short, long)]
+    record: Option<Verdict>,
+
+    /// Submit even if the answer is known to be wrong
+    #[arg(long)]
+    force: bool,
+
+    /// Path to the journal of submitted answers
+    #[arg(short, long, default_value = journal::DEFAULT_JOURNAL_FILE)]
+    journal: PathBuf,
+}
+
+/// Selection of the part(s) to run
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum Part {
+    #[value(name = "1")]
+    One,
+    #[value(name = "2")]
+    Two,
+    Both,
+}
+
+impl Part {
+    /// List the part numbers of this selection
+    fn numbers(self) -> &'static [u8] {
+        match self {
+            Part::One => &[1],
+            Part::Two => &[2],
+            Part::Both => &[1, 2],
+        }
+    }
+}
+
+/// Accept only the numbers of registered days, listing their titles in the help
+fn day_parser() -> impl TypedValueParser<Value = u8> {
+    let days = days::DAYS
+        .iter()
+        .map(|info| PossibleValue::new(info.day.to_string()).help(info.title));
+    PossibleValuesParser::new(days).map(|day| day.parse::<u8>().expect("Day numbers are valid"))
+}
+
+/// Describe the parameters of every day that has any
+fn params_help() -> String {
+    let mut help =
+        String::from("Set a parameter of the solver, like `limit=10` (repeat for more)\n");
+    for info in days::DAYS.iter().filter(|info| !info.params.is_empty()) {
+        let _ = write!(help, "\nDay {}:", info.day);
+        for spec in info.params {
+            let _ = write!(help, "\n  {spec}");
+        }
+    }
+    help
+}
+
+/// Output format for the answers
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq)]
+enum Format {
+    /// Plain answers, or a table for multiple days
+    Text,
+    /// Array of objects, including timings and errors
+    Json,
+    /// Header line and a row per day and part
+    Csv,
 }
 
-fn main() {
+impl Format {
+    /// Print reports in a machine-readable format, returns `false` for plain text
+    fn print_reports(self, reports: &[Report]) -> bool {
+        match self {
+            Format::Text => return false,
+            Format::Json => println!("{}", output::to_json(reports)),
+            Format::Csv => print!("{}", output::to_csv(reports)),
+        }
+        true
+    }
+}
+
+fn main() -> ExitCode {
     let args = Args::parse();
 
-    // Resolve input path
-    let input_file: PathBuf = if args.input.to_str() == Some("default") {
-        PathBuf::from(format!("./inputs/day_{:02}.txt", args.day))
-    } else {
-        args.input
+    match args.command {
+        Some(Command::All { part, format, jobs }) => run_all(part, format, jobs),
+        Some(Command::List) => {
+            runner::print_days(days::DAYS);
+            ExitCode::SUCCESS
+        }
+        Some(Command::Bench(bench_args)) => run_bench(bench_args),
+        Some(Command::Verify { part, answers }) => run_verify(part, answers),
+        Some(Command::Samples { day }) => run_samples(day),
+        Some(Command::Watch {
+            day,
+            part,
+            input,
+            interval,
+        }) => run_watch(day, part, &input, Duration::from_millis(interval)),
+        Some(Command::Submit(submit_args)) => run_submit(&submit_args),
+        Some(Command::New { day, title }) => run_new(day, title.as_deref()),
+        None => run_single(&args),
+    }
+}
+
+/// Run a single day, for one or both parts
+fn run_single(args: &Args) -> ExitCode {
+    let day = args.day.expect("Day is required without a subcommand");
+
+    // Resolve input and read it once for all parts
+    if args.input.to_str() == Some("default") {
+        fetch_missing(&[day]);
+    }
+    let source = match InputSource::from_arg(&args.input, day) {
+        InputSource::File(path) => InputSource::File(path::absolute(path).unwrap()),
+        InputSource::Stdin => InputSource::Stdin,
     };
-    let input_file = path::absolute(&input_file).unwrap();
+    let input = source.read();
 
-    let time_start = if args.timing {
-        Some(Instant::now())
-    } else {
-        None
+    let trace = match &args.trace_file {
+        Some(path) => match Trace::to_file(args.debug, path) {
+            Ok(trace) => trace,
+            Err(err) => {
+                eprintln!("Failed to create {}: {err}", path.display());
+                return ExitCode::from(2);
+            }
+        },
+        None => Trace::to_stderr(args.debug),
     };
 
     // Instantiate the solver for the selected day
-    let solver = get_solver(args.day);
+    let info = days::find_day(day).expect("Day is validated by the CLI");
+    let solver = match info.create(&args.params) {
+        Ok(solver) => solver,
+        Err(err) => Args::command().error(ErrorKind::InvalidValue, err).exit(),
+    };
 
-    let result = solver.run(input_file, args.part);
+    // Parse once for all parts
+    let time_start = Instant::now();
+    let parsed = input.map_err(|err| err.to_string()).and_then(|input| {
+        solver
+            .parse_input(&input, &trace)
+            .map_err(|err| err.with_file(source.label()).to_string())
+    });
+    let time_parse = time_start.elapsed();
+    if args.timing && args.format == Format::Text {
+        eprintln!("Parse time: {:.4} ms", time_parse.as_secs_f32() * 1.0e3);
+    }
 
-    if let Some(time_start) = time_start {
+    let mut reports = Vec::new();
+    for &part in args.part.numbers() {
+        let time_start = Instant::now();
+        let result = parsed.as_ref().map_err(Clone::clone).and_then(|parsed| {
+            solver
+                .solve_part(parsed, part, &trace)
+                .map_err(|err| err.with_file(source.label()).to_string())
+        });
         let elapsed = time_start.elapsed();
-        eprintln!("Computation time: {:.4} ms", elapsed.as_secs_f32() * 1.0e3);
+
+        if args.format == Format::Text {
+            if args.timing {
+                eprintln!("Computation time: {:.4} ms", elapsed.as_secs_f32() * 1.0e3);
+            }
+            match &result {
+                Ok(outcome) => {
+                    println!("{outcome}");
+                    if args.input.to_str() == Some("default") {
+                        warn_known_wrong(day, part, outcome);
+                    }
+                }
+                Err(err) => {
+                    eprintln!("Error in day {day}, part {part}: {err}");
+                    return ExitCode::FAILURE;
+                }
+            }
+        }
+
+        reports.push(Report {
+            day,
+            part,
+            input: source.label().to_path_buf(),
+            result,
+            elapsed: time_parse + elapsed, // Time to get this answer from the input text
+        });
+    }
+
+    let _ = args.format.print_reports(&reports);
+    if reports.iter().any(|report| report.result.is_err()) {
+        return ExitCode::FAILURE;
+    }
+    ExitCode::SUCCESS
+}
+
+/// Run all days and print a table of the answers
+fn run_all(part: Part, format: Format, jobs: usize) -> ExitCode {
+    fetch_missing(&days::day_numbers().collect::<Vec<u8>>());
+    let jobs = match jobs {
+        0 => thread::available_parallelism().map_or(1, usize::from),
+        jobs => jobs,
+    };
+    let reports = runner::run_all(part.numbers(), jobs);
+    if !format.print_reports(&reports) {
+        runner::print_table(&reports);
+    }
+    if reports.iter().any(|report| report.result.is_err()) {
+        return ExitCode::FAILURE;
+    }
+    ExitCode::SUCCESS
+}
+
+/// Benchmark one or all days
+fn run_bench(args: BenchArgs) -> ExitCode {
+    let days: Vec<u8> = match args.day {
+        Some(day) => vec![day],
+        None => days::day_numbers().collect(),
+    };
+    fetch_missing(&days);
+    let baseline = match args.baseline.map(|path| Baseline::load(&path)) {
+        Some(Ok(baseline)) => baseline,
+        Some(Err(err)) => {
+            eprintln!("{err}");
+            return ExitCode::from(2);
+        }
+        None => Baseline::default(),
+    };
+    let config = BenchConfig {
+        warmup: args.warmup,
+        runs: args.runs,
+        profile_alloc: args.profile_alloc,
+    };
+
+    let benchmarks = bench::bench_days(&days, args.part.numbers(), config);
+    let regression = bench::print_benchmarks(&benchmarks, &baseline, args.threshold / 100.0);
+    if args.profile_alloc {
+        println!();
+        bench::print_allocations(&benchmarks);
+    }
+
+    if let Some(path) = args.save
+        && let Err(err) = Baseline::from_benchmarks(&benchmarks).save(&path)
+    {
+        eprintln!("{err}");
+        return ExitCode::from(2);
+    }
+    if regression || benchmarks.iter().any(|b| b.result.is_err()) {
+        return ExitCode::FAILURE;
+    }
+    ExitCode::SUCCESS
+}
+
+/// Run all days and compare against the answers manifest
+fn run_verify(part: Part, answers: PathBuf) -> ExitCode {
+    let answers = match Answers::from_file(&answers::answers_file(answers)) {
+        Ok(answers) => answers,
+        Err(err) => {
+            eprintln!("{err}");
+            return ExitCode::from(2);
+        }
+    };
+    let expected: Vec<u8> = days::day_numbers()
+        .filter(|&day| {
+            part.numbers()
+                .iter()
+                .any(|&p| answers.get(day, p).is_some())
+        })
+        .collect();
+    fetch_missing(&expected);
+    let verifications = answers::verify(&answers, part.numbers(), |day| {
+        InputSource::File(runner::default_input_file(day))
+    });
+    answers::print_verifications(&verifications);
+    if verifications
+        .iter()
+        .any(|v| matches!(v.status, Status::Fail { .. } | Status::Error(_)))
+    {
+        return ExitCode::FAILURE;
+    }
+    ExitCode::SUCCESS
+}
+
+/// Scaffold a new day in the current directory
+fn run_new(day: u8, title: Option<&str>) -> ExitCode {
+    match scaffold::new_day(Path::new("."), day, title) {
+        Ok(written) => {
+            for path in written {
+                println!("Wrote {}", path.display());
+            }
+            println!("Add the puzzle example to the sample files and rebuild to run day {day}");
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            eprintln!("{err}");
+            ExitCode::from(2)
+        }
+    }
+}
+
+/// Download the inputs that are not cached yet, if a session token is set
+///
+/// Failures are only reported, running the day then gives the usual error for a missing input.
+fn fetch_missing(days: &[u8]) {
+    let cache = InputCache::from_env();
+    if cache.can_fetch() {
+        for err in cache.ensure_all(days) {
+            eprintln!("{err}");
+        }
+    }
+}
+
+/// Solve a part and submit the answer, guarded by the journal of earlier attempts
+fn run_submit(args: &SubmitArgs) -> ExitCode {
+    let (day, part) = (args.day, args.part);
+    fetch_missing(&[day]);
+    let source = InputSource::File(runner::default_input_file(day));
+    let answer = match runner::run_part(day, part, &source).result {
+        Ok(answer) => answer,
+        Err(err) => {
+            eprintln!("Error in day {day}, part {part}: {err}");
+            return ExitCode::FAILURE;
+        }
+    };
+    println!("{answer}");
+
+    let mut journal = match Journal::from_file(&args.journal) {
+        Ok(journal) => journal,
+        Err(err) => {
+            eprintln!("{err}");
+            return ExitCode::from(2);
+        }
+    };
+    let verdict = if let Some(verdict) = args.record {
+        journal.record(day, part, &answer, verdict);
+        Ok(verdict)
+    } else {
+        let Some(submitter) = HttpSubmitter::from_env() else {
+            eprintln!("Set {SESSION_VAR} to submit answers");
+            return ExitCode::from(2);
+        };
+        journal::submit(&mut journal, &submitter, (day, part), &answer, args.force)
+    };
+
+    match verdict.and_then(|verdict| journal.save(&args.journal).map(|()| verdict)) {
+        Ok(verdict) => {
+            println!("Answer is {verdict}");
+            if verdict == Verdict::Right {
+                ExitCode::SUCCESS
+            } else {
+                ExitCode::FAILURE
+            }
+        }
+        Err(err) => {
+            eprintln!("{err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Warn when the journal knows an answer for the real input is wrong
+fn warn_known_wrong(day: u8, part: u8, answer: &Outcome) {
+    let Ok(journal) = Journal::from_file(Path::new(journal::DEFAULT_JOURNAL_FILE)) else {
+        return; // Only a hint, the run itself is fine
+    };
+    let check = journal.check(day, part, answer);
+    if check.is_wrong() {
+        eprintln!("Warning: {check}");
+    }
+}
+
+/// Check the samples of one or all days
+fn run_samples(day: Option<u8>) -> ExitCode {
+    let samples = match samples::discover(Path::new(SAMPLES_DIR)) {
+        Ok(samples) => samples,
+        Err(err) => {
+            eprintln!("{err}");
+            return ExitCode::from(2);
+        }
+    };
+    let selected: Vec<_> = samples
+        .iter()
+        .filter(|sample| day.is_none_or(|day| sample.day == day))
+        .collect();
+    let failures: Vec<String> = selected.iter().flat_map(|sample| sample.check()).collect();
+    for failure in &failures {
+        println!("{failure}");
+    }
+    println!("{} samples, {} failures", selected.len(), failures.len());
+    if failures.is_empty() {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
     }
+}
 
-    println!("{result}");
+/// Re-run a day on every change to its files, until interrupted
+fn run_watch(day: u8, part: Part, input: &Path, interval: Duration) -> ExitCode {
+    if input.to_str() == Some("default") {
+        fetch_missing(&[day]);
+    }
+    let InputSource::File(input) = InputSource::from_arg(input, day) else {
+        eprintln!("Cannot watch stdin, give an input file");
+        return ExitCode::from(2);
+    };
+    let paths = watch::watched_paths(day, &input);
+    let names: Vec<String> = paths.iter().map(|p| p.display().to_string()).collect();
+    println!("Watching {}, stop with Ctrl-C", names.join(", "));
+
+    let mut snapshot: Option<Snapshot> = None;
+    let mut previous = None;
+    for run in 1.. {
+        let current = loop {
+            let current = Snapshot::take(&paths);
+            match &snapshot {
+                Some(earlier) if current.changes(earlier).is_empty() => thread::sleep(interval),
+                _ => break current,
+            }
+        };
+        if let Some(earlier) = &snapshot {
+            let changed: Vec<String> = current
+                .changes(earlier)
+                .iter()
+                .map(|p| p.display().to_string())
+                .collect();
+            println!("\nChanged: {}", changed.join(", "));
+        }
+        snapshot = Some(current);
+
+        let samples = match watch::run_samples(day) {
+            Ok(true) => "pass",
+            Ok(false) => "FAIL",
+            Err(err) => {
+                eprintln!("{err}");
+                return ExitCode::from(2);
+            }
+        };
+        match watch::run_answers(day, part.numbers(), &input) {
+            Ok(answers) => {
+                let summary = watch::summary(&answers, previous.as_ref());
+                println!("Run {run}: samples {samples} | {summary}");
+                previous = Some(answers);
+            }
+            Err(err) => println!("Run {run}: samples {samples} | {err}"),
+        }
+    }
+    ExitCode::SUCCESS
 }
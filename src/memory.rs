@@ -0,0 +1,150 @@
+//! Counting of heap allocations, to find the solvers that allocate the most
+//!
+//! The executable installs [`CountingAllocator`] as its global allocator. It only counts inside
+//! [`measure`], other allocations cost no more than checking a flag.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::hint;
+use std::sync::atomic::{AtomicBool, AtomicI64, AtomicU64, Ordering};
+use std::sync::{Mutex, PoisonError};
+
+static COUNTING: AtomicBool = AtomicBool::new(false);
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static BYTES: AtomicU64 = AtomicU64::new(0);
+static IN_USE: AtomicI64 = AtomicI64::new(0); // Below zero after freeing older memory
+static PEAK: AtomicI64 = AtomicI64::new(0);
+static MEASURE_LOCK: Mutex<()> = Mutex::new(()); // Only one measurement uses the counters
+
+/// Allocator that passes everything to the system allocator, counting while measuring
+pub struct CountingAllocator;
+
+impl CountingAllocator {
+    /// Count an allocation of `size` bytes, in place of one of `freed` bytes
+    fn record(size: usize, freed: usize) {
+        if !COUNTING.load(Ordering::Relaxed) {
+            return;
+        }
+        if size > 0 {
+            ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+            BYTES.fetch_add(size as u64, Ordering::Relaxed);
+        }
+        let change = i64::try_from(size).unwrap_or(i64::MAX) - i64::try_from(freed).unwrap_or(0);
+        let in_use = IN_USE.fetch_add(change, Ordering::Relaxed) + change;
+        PEAK.fetch_max(in_use, Ordering::Relaxed);
+    }
+}
+
+// SAFETY: All requests are passed on to `System` unchanged
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() {
+            Self::record(layout.size(), 0);
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc_zeroed(layout) };
+        if !ptr.is_null() {
+            Self::record(layout.size(), 0);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+        Self::record(0, layout.size());
+    }
+
+    /// A reallocation counts as a new allocation of the new size
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = unsafe { System.realloc(ptr, layout, new_size) };
+        if !new_ptr.is_null() {
+            Self::record(new_size, layout.size());
+        }
+        new_ptr
+    }
+}
+
+/// Allocations made during a measurement
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AllocStats {
+    pub allocations: u64,
+    pub bytes: u64, // Total of all allocations, including those freed again
+    pub peak: u64,  // Largest growth of the heap in use, compared to the start
+}
+
+/// Stops counting when dropped, also when unwinding from a panic
+struct Counting;
+
+impl Drop for Counting {
+    fn drop(&mut self) {
+        COUNTING.store(false, Ordering::Relaxed);
+    }
+}
+
+/// Run `func` and count the allocations it makes
+///
+/// Counts are global, so allocations by other threads at the same time are included (like those
+/// of worker threads started by `func`). Measurements from different threads wait for each
+/// other, nesting them deadlocks. Without [`CountingAllocator`] installed everything is zero, see
+/// [`is_installed`].
+pub fn measure<R>(func: impl FnOnce() -> R) -> (R, AllocStats) {
+    let _lock = MEASURE_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+    ALLOCATIONS.store(0, Ordering::Relaxed);
+    BYTES.store(0, Ordering::Relaxed);
+    IN_USE.store(0, Ordering::Relaxed);
+    PEAK.store(0, Ordering::Relaxed);
+
+    let counting = Counting;
+    COUNTING.store(true, Ordering::Relaxed);
+    let result = func();
+    drop(counting);
+
+    let stats = AllocStats {
+        allocations: ALLOCATIONS.load(Ordering::Relaxed),
+        bytes: BYTES.load(Ordering::Relaxed),
+        peak: u64::try_from(PEAK.load(Ordering::Relaxed)).unwrap_or(0),
+    };
+    (result, stats)
+}
+
+/// Whether [`CountingAllocator`] is the global allocator of this executable
+#[must_use]
+pub fn is_installed() -> bool {
+    let (_, stats) = measure(|| hint::black_box(Box::new(0_u64)));
+    stats.allocations > 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[global_allocator]
+    static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+    #[test]
+    fn measure_allocations() {
+        assert!(is_installed());
+
+        // Allocations of other tests running at the same time are counted too, so only check
+        // the lower bounds. No other measurement can reset the counters halfway.
+        let (list, stats) = measure(|| {
+            let mut list: Vec<u64> = Vec::with_capacity(1000);
+            list.extend(0..2000);
+            list
+        });
+        assert_eq!(list.len(), 2000);
+        assert!(stats.allocations >= 2);
+        assert!(stats.bytes >= 8 * 3000);
+
+        let threads: Vec<_> = (0..4)
+            .map(|_| std::thread::spawn(|| measure(|| hint::black_box(vec![0_u8; 100])).1))
+            .collect();
+        for thread in threads {
+            let stats = thread.join().unwrap();
+            assert!(stats.allocations >= 1 && stats.bytes >= 100);
+        }
+    }
+}
@@ -0,0 +1,135 @@
+//! Machine-readable output of run reports, as JSON or CSV
+
+use crate::runner::Report;
+use crate::shared::Outcome;
+use serde::Serialize;
+
+/// Flat form of a [`Report`], as it is written out
+#[derive(Debug, Serialize)]
+struct Record<'a> {
+    day: u8,
+    part: u8,
+    input: String,
+    outcome: Option<&'a Outcome>,
+    error: Option<&'a str>,
+    elapsed_ms: f64,
+}
+
+impl<'a> From<&'a Report> for Record<'a> {
+    fn from(report: &'a Report) -> Self {
+        Self {
+            day: report.day,
+            part: report.part,
+            input: report.input.display().to_string(),
+            outcome: report.result.as_ref().ok(),
+            error: report.result.as_ref().err().map(String::as_str),
+            elapsed_ms: report.elapsed.as_secs_f64() * 1.0e3,
+        }
+    }
+}
+
+/// Format reports as a JSON array, one object per day and part
+///
+/// # Panics
+///
+/// If serialization fails, which cannot happen for these plain records.
+#[must_use]
+pub fn to_json(reports: &[Report]) -> String {
+    let records: Vec<Record> = reports.iter().map(Record::from).collect();
+    serde_json::to_string_pretty(&records).expect("Records are always serializable")
+}
+
+/// Format reports as CSV with a header line, one row per day and part
+#[must_use]
+pub fn to_csv(reports: &[Report]) -> String {
+    let mut csv = String::from("day,part,input,type,answer,elapsed_ms,error\n");
+    for report in reports {
+        let record = Record::from(report);
+        let (kind, answer) = match record.outcome {
+            Some(outcome) => (outcome.kind(), outcome.to_string()),
+            None => ("", String::new()),
+        };
+        let row = [
+            record.day.to_string(),
+            record.part.to_string(),
+            csv_field(&record.input),
+            kind.to_string(),
+            csv_field(&answer),
+            record.elapsed_ms.to_string(),
+            csv_field(record.error.unwrap_or_default()),
+        ];
+        csv.push_str(&row.join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+/// Quote a CSV field when it contains a separator, quote or line break
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    use std::time::Duration;
+
+    fn sample_reports() -> Vec<Report> {
+        vec![
+            Report {
+                day: 1,
+                part: 2,
+                input: PathBuf::from("tests/day_01/sample.txt"),
+                result: Ok(Outcome::U64(6)),
+                elapsed: Duration::from_micros(1500),
+            },
+            Report {
+                day: 3,
+                part: 1,
+                input: PathBuf::from("<stdin>"),
+                result: Err(String::from(
+                    "line 2, column 1: Expected \"digit\", got `a`",
+                )),
+                elapsed: Duration::ZERO,
+            },
+        ]
+    }
+
+    #[test]
+    fn reports_to_json() {
+        let json: serde_json::Value = serde_json::from_str(&to_json(&sample_reports())).unwrap();
+        assert_eq!(
+            json[0],
+            serde_json::json!({
+                "day": 1,
+                "part": 2,
+                "input": "tests/day_01/sample.txt",
+                "outcome": {"type": "U64", "value": 6},
+                "error": null,
+                "elapsed_ms": 1.5,
+            })
+        );
+        assert_eq!(json[1]["outcome"], serde_json::Value::Null);
+        assert_eq!(
+            json[1]["error"],
+            "line 2, column 1: Expected \"digit\", got `a`"
+        );
+    }
+
+    #[test]
+    fn reports_to_csv() {
+        let csv = to_csv(&sample_reports());
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], "day,part,input,type,answer,elapsed_ms,error");
+        assert_eq!(lines[1], "1,2,tests/day_01/sample.txt,U64,6,1.5,");
+        assert_eq!(
+            lines[2],
+            "3,1,<stdin>,,,0,\"line 2, column 1: Expected \"\"digit\"\", got `a`\""
+        );
+    }
+}
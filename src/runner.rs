@@ -0,0 +1,294 @@
+use crate::days::{self, DayInfo, get_solver};
+use crate::fetch;
+use crate::shared::{Outcome, SolveError, Trace, read_input};
+use std::any::Any;
+use std::cell::Cell;
+use std::fs::File;
+use std::io;
+use std::panic;
+use std::path::{Path, PathBuf};
+use std::sync::Once;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Result of running the solver for a single day and part
+#[derive(Debug)]
+pub struct Report {
+    pub day: u8,
+    pub part: u8,
+    pub input: PathBuf, // Label of the input, see [`InputSource::label`]
+    pub result: Result<Outcome, String>, // Error holds the error or panic message
+    pub elapsed: Duration,
+}
+
+/// Where to read the puzzle input from
+#[derive(Debug, Clone, PartialEq)]
+pub enum InputSource {
+    Stdin,
+    File(PathBuf),
+}
+
+impl InputSource {
+    /// Resolve the `--input` argument, where `"default"` gives the usual file and `-` is stdin
+    #[must_use]
+    pub fn from_arg(arg: &Path, day: u8) -> Self {
+        match arg.to_str() {
+            Some("default") => InputSource::File(default_input_file(day)),
+            Some("-") => InputSource::Stdin,
+            _ => InputSource::File(arg.to_path_buf()),
+        }
+    }
+
+    /// Name of the input to use in messages
+    #[must_use]
+    pub fn label(&self) -> &Path {
+        match self {
+            InputSource::Stdin => Path::new("<stdin>"),
+            InputSource::File(path) => path,
+        }
+    }
+
+    /// Read the complete input text
+    ///
+    /// # Errors
+    ///
+    /// If the input cannot be read, the error includes the file name.
+    pub fn read(&self) -> Result<String, SolveError> {
+        let input = match self {
+            InputSource::Stdin => read_input(io::stdin().lock()),
+            InputSource::File(path) => File::open(path)
+                .map_err(SolveError::from)
+                .and_then(read_input),
+        };
+        input.map_err(|err| err.with_file(self.label()))
+    }
+}
+
+/// Get the default location of the input file for a day
+#[must_use]
+pub fn default_input_file(day: u8) -> PathBuf {
+    Path::new(fetch::DEFAULT_CACHE_DIR).join(format!("day_{day:02}.txt"))
+}
+
+/// Run the solver of a day for one part, catching any panic
+///
+/// The panic message ends up in the report, instead of being printed.
+#[must_use]
+pub fn run_part(day: u8, part: u8, source: &InputSource) -> Report {
+    let solver = get_solver(day);
+    let time_start = Instant::now();
+    let result = without_panic_output(|| {
+        panic::catch_unwind(panic::AssertUnwindSafe(|| {
+            let input = source.read()?;
+            solver
+                .run(&input, part, &Trace::disabled())
+                .map_err(|err| err.with_file(source.label()))
+        }))
+    })
+    .map_err(|payload| format!("panicked: {}", panic_message(payload.as_ref())))
+    .and_then(|result| result.map_err(|err| err.to_string()));
+    let elapsed = time_start.elapsed();
+
+    Report {
+        day,
+        part,
+        input: source.label().to_path_buf(),
+        result,
+        elapsed,
+    }
+}
+
+/// Run every registered day for the given parts, continuing after failures
+///
+/// With more than one job, the days and parts are solved on that many threads. The reports are
+/// in the same order either way, and each has the time of its own solve.
+#[must_use]
+pub fn run_all(parts: &[u8], jobs: usize) -> Vec<Report> {
+    run_all_with(parts, jobs, |day| {
+        InputSource::File(default_input_file(day))
+    })
+}
+
+/// Run every registered day like [`run_all`], `inputs` gives the input to use for each day
+///
+/// # Panics
+///
+/// If a worker thread panics, which cannot happen as panics are caught per task.
+#[must_use]
+pub fn run_all_with(
+    parts: &[u8],
+    jobs: usize,
+    inputs: impl Fn(u8) -> InputSource + Sync,
+) -> Vec<Report> {
+    let tasks: Vec<(u8, u8)> = days::day_numbers()
+        .flat_map(|day| parts.iter().map(move |&part| (day, part)))
+        .collect();
+    let run_task = |&(day, part): &(u8, u8)| run_part(day, part, &inputs(day));
+
+    if jobs <= 1 {
+        return tasks.iter().map(run_task).collect();
+    }
+
+    // Workers take the next task until none are left, reports keep the index of their task
+    let next = AtomicUsize::new(0);
+    let mut reports: Vec<(usize, Report)> = thread::scope(|scope| {
+        let workers: Vec<_> = (0..jobs.min(tasks.len()))
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut done = Vec::new();
+                    loop {
+                        let index = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(task) = tasks.get(index) else {
+                            break done;
+                        };
+                        done.push((index, run_task(task)));
+                    }
+                })
+            })
+            .collect();
+        workers
+            .into_iter()
+            .flat_map(|worker| worker.join().expect("Panics are caught per task"))
+            .collect()
+    });
+    reports.sort_by_key(|(index, _)| *index);
+    reports.into_iter().map(|(_, report)| report).collect()
+}
+
+thread_local! {
+    /// Whether panics on this thread are kept quiet, see [`without_panic_output`]
+    static SILENCED: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Installs the panic hook that checks [`SILENCED`], only once such that threads don't race
+static SILENCING_HOOK: Once = Once::new();
+
+/// Restores the previous silencing of this thread when dropped, also when unwinding
+struct Silenced {
+    previous: bool,
+}
+
+impl Drop for Silenced {
+    fn drop(&mut self) {
+        SILENCED.set(self.previous);
+    }
+}
+
+/// Execute a function without printing the panics of the current thread
+///
+/// Used when the panic message ends up in a report instead. Other threads print their panics as
+/// usual, through the panic hook that was installed before.
+pub fn without_panic_output<T>(func: impl FnOnce() -> T) -> T {
+    SILENCING_HOOK.call_once(|| {
+        let hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !SILENCED.get() {
+                hook(info);
+            }
+        }));
+    });
+    let _silenced = Silenced {
+        previous: SILENCED.replace(true),
+    };
+    func()
+}
+
+/// Print a list of reports as a table
+pub fn print_table(reports: &[Report]) {
+    println!(
+        "{:>3} | {:>4} | {:<20} | {:>12}",
+        "Day", "Part", "Answer", "Time [ms]"
+    );
+    println!("{:-<4}+{:-<6}+{:-<22}+{:-<13}", "", "", "", "");
+    for report in reports {
+        let answer = match &report.result {
+            Ok(outcome) => outcome.to_string().replace('\n', " / "), // Keep grids on one row
+            Err(msg) => format!("FAILED: {msg}"),
+        };
+        println!(
+            "{:>3} | {:>4} | {:<20} | {:>12.4}",
+            report.day,
+            report.part,
+            answer,
+            report.elapsed.as_secs_f64() * 1.0e3
+        );
+    }
+}
+
+/// Print the registered days and whether they are solved
+pub fn print_days(days: &[DayInfo]) {
+    println!("{:>3} | {:<24} | Status", "Day", "Title");
+    println!("{:-<4}+{:-<26}+{:-<10}", "", "", "");
+    for info in days {
+        let status = if info.implemented { "solved" } else { "stub" };
+        println!("{:>3} | {:<24} | {status}", info.day, info.title);
+    }
+}
+
+/// Turn the payload of a caught panic into a readable message
+pub(crate) fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(msg) = payload.downcast_ref::<&str>() {
+        (*msg).to_string()
+    } else if let Some(msg) = payload.downcast_ref::<String>() {
+        msg.clone()
+    } else {
+        String::from("unknown panic")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn run_part_sample() {
+        let source = InputSource::File(PathBuf::from("tests/day_01/sample.txt"));
+        let report = run_part(1, 2, &source);
+        assert_eq!((report.day, report.part), (1, 2));
+        assert_eq!(report.result, Ok(Outcome::U64(6)));
+    }
+
+    #[test]
+    fn run_all_in_parallel() {
+        let samples =
+            |day: u8| InputSource::File(PathBuf::from(format!("tests/day_{day:02}/sample.txt")));
+        let sequential = run_all_with(&[1, 2], 1, samples);
+        let parallel = run_all_with(&[1, 2], 4, samples);
+        let outcomes = |reports: &[Report]| -> Vec<(u8, u8, Result<Outcome, String>)> {
+            reports
+                .iter()
+                .map(|r| (r.day, r.part, r.result.clone()))
+                .collect()
+        };
+        assert_eq!(outcomes(&parallel), outcomes(&sequential));
+        assert_eq!(parallel.len(), 2 * days::DAYS.len());
+        assert_eq!((parallel[1].day, parallel[1].part), (1, 2));
+        assert_eq!(parallel[1].result, Ok(Outcome::U64(6)));
+        let solved = parallel.iter().filter(|r| r.result.is_ok()).count();
+        assert!(solved >= 20, "Only {solved} samples were solved");
+    }
+
+    #[test]
+    fn panic_output_silenced_per_thread() {
+        without_panic_output(|| {
+            let caught = panic::catch_unwind(|| without_panic_output(|| panic!("quiet")));
+            assert!(caught.is_err());
+            assert!(SILENCED.get()); // Still silenced after the inner one unwound
+            assert!(!thread::spawn(|| SILENCED.get()).join().unwrap());
+        });
+        assert!(!SILENCED.get());
+    }
+
+    #[test]
+    fn run_part_reports_error() {
+        let source = InputSource::File(PathBuf::from("tests/day_01/missing.txt"));
+        let report = run_part(1, 1, &source);
+        assert!(
+            report
+                .result
+                .unwrap_err()
+                .starts_with("tests/day_01/missing.txt: ")
+        );
+    }
+}
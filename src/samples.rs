@@ -0,0 +1,188 @@
+//! Sample inputs with expected answers, checked by one generic test
+//!
+//! Every `day_<nn>/<name>.txt` below the samples directory needs a sidecar `<name>.toml` like:
+//!
+//! ```toml
+//! part_1 = 40
+//! part_2 = 25272
+//!
+//! [params]
+//! limit = 10
+//! ```
+//!
+//! Parts that are left out are not checked, such that a sidecar without answers marks a sample
+//! that is not solved yet. `[params]` overrides the defaults of the day.
+
+use crate::answers::{number_from_key, outcome_from_value};
+use crate::days;
+use crate::shared::{Outcome, Trace};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Default location of the sample inputs
+pub const SAMPLES_DIR: &str = "./tests";
+
+/// A sample input file with the expected answers from its sidecar
+#[derive(Debug)]
+pub struct Sample {
+    pub day: u8,
+    pub input: PathBuf,
+    pub params: Vec<String>,          // Assignments like `limit=10`
+    pub expected: Vec<(u8, Outcome)>, // Part and its expected answer
+}
+
+impl Sample {
+    /// Load a sample from its input file, reading the `.toml` sidecar next to it
+    ///
+    /// # Errors
+    ///
+    /// If the sidecar is missing or invalid.
+    pub fn load(day: u8, input: &Path) -> Result<Self, String> {
+        let sidecar = input.with_extension("toml");
+        let content = fs::read_to_string(&sidecar)
+            .map_err(|err| format!("Failed to read {}: {err}", sidecar.display()))?;
+        Self::parse(day, input, &content).map_err(|err| format!("In {}: {err}", sidecar.display()))
+    }
+
+    /// Parse the content of a sidecar for an input file
+    ///
+    /// # Errors
+    ///
+    /// If the content is not valid TOML or does not follow the sidecar layout.
+    pub fn parse(day: u8, input: &Path, content: &str) -> Result<Self, String> {
+        let table: toml::Table = content.parse().map_err(|err| format!("{err}"))?;
+        let mut params = Vec::new();
+        let mut expected = Vec::new();
+
+        for (key, value) in &table {
+            if key == "params" {
+                let values = value
+                    .as_table()
+                    .ok_or_else(|| String::from("Expected a table for `params`"))?;
+                for (name, value) in values {
+                    let value = value
+                        .as_integer()
+                        .ok_or_else(|| format!("Expected a whole number for `params.{name}`"))?;
+                    params.push(format!("{name}={value}"));
+                }
+            } else {
+                let part = number_from_key(key, "part_")?;
+                if !(1..=2).contains(&part) {
+                    return Err(format!("Invalid part in `{key}`, expected 1 or 2"));
+                }
+                let outcome = outcome_from_value(value)
+                    .ok_or_else(|| format!("Unsupported value for `{key}`"))?;
+                expected.push((part, outcome));
+            }
+        }
+
+        Ok(Self {
+            day,
+            input: input.to_path_buf(),
+            params,
+            expected,
+        })
+    }
+
+    /// Run the solver on this sample, giving a message for each mismatch or error
+    #[must_use]
+    pub fn check(&self) -> Vec<String> {
+        let label = self.input.display();
+        let Some(info) = days::find_day(self.day) else {
+            return vec![format!("{label}: no solver for day {}", self.day)];
+        };
+        let solver = match info.create(&self.params) {
+            Ok(solver) => solver,
+            Err(err) => return vec![format!("{label}: {err}")],
+        };
+        let input = match fs::read_to_string(&self.input) {
+            Ok(input) => input,
+            Err(err) => return vec![format!("{label}: {err}")],
+        };
+        let trace = Trace::disabled();
+        let parsed = match solver.parse_input(&input, &trace) {
+            Ok(parsed) => parsed,
+            Err(err) => return vec![format!("{label}: {err}")],
+        };
+
+        let mut failures = Vec::new();
+        for (part, expected) in &self.expected {
+            match solver.solve_part(&parsed, *part, &trace) {
+                Ok(actual) if actual == *expected => {}
+                Ok(actual) => failures.push(format!(
+                    "{label}: part {part}: got {actual}, expected {expected}"
+                )),
+                Err(err) => failures.push(format!("{label}: part {part}: {err}")),
+            }
+        }
+        failures
+    }
+}
+
+/// Find all samples in the `day_<nn>` directories below `root`, sorted by path
+///
+/// # Errors
+///
+/// If a directory cannot be read, or a sample has no valid sidecar.
+pub fn discover(root: &Path) -> Result<Vec<Sample>, String> {
+    let mut inputs = Vec::new();
+    for entry in read_dir(root)? {
+        let name = entry.file_name().to_string_lossy().into_owned();
+        let Ok(day) = number_from_key(&name, "day_") else {
+            continue;
+        };
+        if !entry.path().is_dir() {
+            continue;
+        }
+        for file in read_dir(&entry.path())? {
+            let path = file.path();
+            if path.extension().is_some_and(|ext| ext == "txt") {
+                inputs.push((day, path));
+            }
+        }
+    }
+    inputs.sort();
+    inputs
+        .iter()
+        .map(|(day, path)| Sample::load(*day, path))
+        .collect()
+}
+
+/// List a directory, with the path in the error
+fn read_dir(path: &Path) -> Result<Vec<fs::DirEntry>, String> {
+    fs::read_dir(path)
+        .and_then(Iterator::collect)
+        .map_err(|err| format!("Failed to read {}: {err}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_sidecar() {
+        let input = Path::new("tests/day_08/sample.txt");
+        let sample = Sample::parse(8, input, "part_2 = 25272\n\n[params]\nlimit = 10\n").unwrap();
+        assert_eq!(sample.params, vec![String::from("limit=10")]);
+        assert_eq!(sample.expected, vec![(2, Outcome::U64(25272))]);
+        assert!(sample.check().is_empty());
+
+        assert!(Sample::parse(8, input, "").unwrap().expected.is_empty());
+        assert!(Sample::parse(8, input, "part_3 = 1\n").is_err());
+        assert!(Sample::parse(8, input, "part_1 = 1\n[params]\nlimit = \"x\"\n").is_err());
+    }
+
+    #[test]
+    fn check_mismatch() {
+        let input = Path::new("tests/day_08/sample.txt");
+        let sample = Sample::parse(8, input, "part_1 = 41\n[params]\nlimit = 10\n").unwrap();
+        assert_eq!(
+            sample.check(),
+            vec![String::from(
+                "tests/day_08/sample.txt: part 1: got 40, expected 41"
+            )]
+        );
+        let sample = Sample::parse(8, input, "part_1 = 40\n[params]\nsize = 10\n").unwrap();
+        assert_eq!(sample.check().len(), 1);
+    }
+}
@@ -0,0 +1,249 @@
+//! Generate the files for a new day from a template
+
+use crate::days;
+use std::fs;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+/// Source of a new solver, `DD` is the zero-padded day and `TITLE` a string literal
+const SOLVER_TEMPLATE: &str = r#"use crate::days::DayInfo;
+use crate::shared::{Outcome, Solution, SolveError, SolveResult, Trace};
+
+pub struct DayDD;
+
+/// Registration of the solver for day DAY
+pub const INFO: DayInfo = DayInfo {
+    day: DAY,
+    title: TITLE,
+    implemented: false,
+    params: &[],
+    solver: |_| Box::new(DayDD),
+};
+
+impl Solution for DayDD {
+    type Parsed = Vec<String>;
+
+    fn parse(&self, input: &str, _trace: &Trace) -> Result<Self::Parsed, SolveError> {
+        Ok(input.lines().map(String::from).collect())
+    }
+
+    fn part_1(&self, _lines: &Self::Parsed, _trace: &Trace) -> SolveResult {
+        Ok(Outcome::Text(String::from("dDDp1")))
+    }
+
+    fn part_2(&self, _lines: &Self::Parsed, _trace: &Trace) -> SolveResult {
+        Ok(Outcome::Text(String::from("dDDp2")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn parse_sample() {
+        let input = fs::read_to_string("tests/day_DD/sample.txt").unwrap();
+        assert!(DayDD.parse(&input, &Trace::disabled()).is_ok());
+    }
+}
+"#;
+
+/// Sidecar of the sample, without answers such that nothing is checked yet
+const SIDECAR_TEMPLATE: &str = "# Expected answers for sample.txt, see tests/README.md\n\
+                                # part_1 = 0\n\
+                                # part_2 = 0\n";
+
+/// Create the solver module, the sample files and the registration of a day below `root`
+///
+/// A placeholder module (with `implemented: false`) is replaced, its title is kept unless a new
+/// one is given. Existing sample files are left alone. Returns the files that were written.
+///
+/// # Errors
+///
+/// If `root` is not the repository, the day is already implemented or a file cannot be written.
+pub fn new_day(root: &Path, day: u8, title: Option<&str>) -> Result<Vec<PathBuf>, String> {
+    let registry = root.join("src/days/mod.rs");
+    if !registry.is_file() {
+        return Err(format!(
+            "No {} found, run from the repository root",
+            registry.display()
+        ));
+    }
+
+    let module = root.join(format!("src/days/day_{day:02}.rs"));
+    if let Ok(source) = fs::read_to_string(&module)
+        && !source.contains("implemented: false")
+    {
+        return Err(format!(
+            "Day {day} is already implemented in {}",
+            module.display()
+        ));
+    }
+
+    let title = title
+        .map(String::from)
+        .or_else(|| days::find_day(day).map(|info| info.title.to_string()))
+        .unwrap_or_else(|| format!("Day {day}"));
+    let mut written = vec![write(&module, &solver_source(day, &title))?];
+
+    let samples = root.join(format!("tests/day_{day:02}"));
+    fs::create_dir_all(&samples)
+        .map_err(|err| format!("Failed to create {}: {err}", samples.display()))?;
+    for (name, content) in [("sample.txt", ""), ("sample.toml", SIDECAR_TEMPLATE)] {
+        let path = samples.join(name);
+        if !path.exists() {
+            written.push(write(&path, content)?);
+        }
+    }
+
+    let content = fs::read_to_string(&registry)
+        .map_err(|err| format!("Failed to read {}: {err}", registry.display()))?;
+    if let Some(updated) = register(&content, day)? {
+        written.push(write(&registry, &updated)?);
+    }
+
+    Ok(written)
+}
+
+/// Fill in the solver template
+fn solver_source(day: u8, title: &str) -> String {
+    SOLVER_TEMPLATE
+        .replace("DD", &format!("{day:02}"))
+        .replace("DAY", &day.to_string())
+        .replace("TITLE", &format!("{title:?}"))
+}
+
+/// Declare the module of a day and add it to the `register_days!` list, both in order
+///
+/// Gives `None` if the day is already registered.
+fn register(registry: &str, day: u8) -> Result<Option<String>, String> {
+    let declaration = format!("mod day_{day:02};");
+    let entry = format!("    day_{day:02} => Day{day:02},");
+    let mut lines: Vec<&str> = registry.lines().collect();
+    let has_declaration = lines.contains(&declaration.as_str());
+    let has_entry = lines.contains(&entry.as_str());
+    if has_declaration && has_entry {
+        return Ok(None);
+    }
+
+    let first = lines
+        .iter()
+        .position(|line| *line == "register_days! {")
+        .ok_or("No `register_days!` list found")?
+        + 1;
+    let end = first
+        + lines[first..]
+            .iter()
+            .position(|line| *line == "}")
+            .ok_or("Unterminated `register_days!` list")?;
+    if !has_entry {
+        insert_sorted(&mut lines, first..end, &entry);
+    }
+
+    if !has_declaration {
+        let modules: Vec<usize> = (0..lines.len())
+            .filter(|&idx| lines[idx].starts_with("mod day_"))
+            .collect();
+        let (Some(&first), Some(&last)) = (modules.first(), modules.last()) else {
+            return Err(String::from("No `mod day_NN;` declarations found"));
+        };
+        insert_sorted(&mut lines, first..last + 1, &declaration);
+    }
+
+    Ok(Some(lines.join("\n") + "\n"))
+}
+
+/// Insert a line within a range of sorted lines, before the first one that comes after it
+fn insert_sorted<'a>(lines: &mut Vec<&'a str>, range: Range<usize>, line: &'a str) {
+    let at = lines[range.clone()]
+        .iter()
+        .position(|other| *other > line)
+        .map_or(range.end, |offset| range.start + offset);
+    lines.insert(at, line);
+}
+
+/// Write a file, returning its path
+fn write(path: &Path, content: &str) -> Result<PathBuf, String> {
+    fs::write(path, content).map_err(|err| format!("Failed to write {}: {err}", path.display()))?;
+    Ok(path.to_path_buf())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Registry with the layout of the real one
+    const REGISTRY: &str = include_str!("days/mod.rs");
+
+    /// Days that are declared as modules and days in the `register_days!` list
+    fn registered(registry: &str) -> (Vec<&str>, Vec<&str>) {
+        let modules = registry
+            .lines()
+            .filter_map(|line| line.strip_prefix("mod ")?.strip_suffix(';'))
+            .collect();
+        let entries = registry
+            .lines()
+            .filter_map(|line| {
+                let (module, _) = line.trim().split_once(" => ")?;
+                module.starts_with("day_").then_some(module)
+            })
+            .collect();
+        (modules, entries)
+    }
+
+    #[test]
+    fn register_in_order() {
+        let registry = "mod day_01;\nmod day_03;\n\nregister_days! {\n    day_01 => Day01,\n    \
+                        day_03 => Day03,\n}\n";
+        assert_eq!(
+            register(registry, 2).unwrap().unwrap(),
+            "mod day_01;\nmod day_02;\nmod day_03;\n\nregister_days! {\n    day_01 => Day01,\n    \
+             day_02 => Day02,\n    day_03 => Day03,\n}\n"
+        );
+        let updated = register(registry, 4).unwrap().unwrap();
+        assert!(updated.contains("mod day_03;\nmod day_04;\n\n"));
+        assert!(updated.ends_with("Day03,\n    day_04 => Day04,\n}\n"));
+        assert_eq!(register(registry, 3).unwrap(), None);
+        assert!(register("", 3).is_err());
+        assert!(register("register_days! {\n}\n", 3).is_err());
+    }
+
+    #[test]
+    fn register_real_layout() {
+        let (modules, entries) = registered(REGISTRY);
+        assert_eq!(modules, entries);
+        assert_eq!(register(REGISTRY, 1).unwrap(), None);
+
+        let updated = register(REGISTRY, 13).unwrap().unwrap();
+        let (modules, entries) = registered(&updated);
+        assert_eq!(modules, entries);
+        assert_eq!(modules.last(), Some(&"day_13"));
+        assert_eq!(register(&updated, 13).unwrap(), None);
+    }
+
+    #[test]
+    fn new_day_files() {
+        let root = std::env::temp_dir().join(format!("aoc_scaffold_{}", std::process::id()));
+        fs::create_dir_all(root.join("src/days")).unwrap();
+        fs::write(root.join("src/days/mod.rs"), REGISTRY).unwrap();
+
+        let written = new_day(&root, 14, Some("Say \"hi\"")).unwrap();
+        assert_eq!(written.len(), 4);
+        let source = fs::read_to_string(root.join("src/days/day_14.rs")).unwrap();
+        assert!(source.contains("pub struct Day14;"));
+        assert!(source.contains("title: \"Say \\\"hi\\\"\","));
+        assert!(source.contains("\"tests/day_14/sample.txt\""));
+        let registry = fs::read_to_string(root.join("src/days/mod.rs")).unwrap();
+        assert!(registry.contains("    day_14 => Day14,\n"));
+        assert!(registry.contains("mod day_12;\nmod day_14;\n"));
+
+        // Placeholder can be regenerated, but not once it is implemented
+        assert_eq!(new_day(&root, 14, None).unwrap().len(), 1);
+        let solved = source.replace("implemented: false", "implemented: true");
+        fs::write(root.join("src/days/day_14.rs"), solved).unwrap();
+        assert!(new_day(&root, 14, None).is_err());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
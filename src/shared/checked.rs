@@ -0,0 +1,86 @@
+use crate::shared::SolveError;
+
+/// Integer types with arithmetic that reports overflow
+pub trait CheckedNumber: Copy {
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn try_add(self, other: Self) -> Option<Self>;
+    fn try_mul(self, other: Self) -> Option<Self>;
+}
+
+macro_rules! impl_checked_number {
+    ($($t:ty),*) => {
+        $(
+            impl CheckedNumber for $t {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+
+                fn try_add(self, other: Self) -> Option<Self> {
+                    self.checked_add(other)
+                }
+
+                fn try_mul(self, other: Self) -> Option<Self> {
+                    self.checked_mul(other)
+                }
+            }
+        )*
+    };
+}
+
+impl_checked_number!(u64, i64, u128);
+
+/// Sum and product of an iterator, giving an error instead of wrapping around
+pub trait CheckedIterator: Iterator + Sized
+where
+    Self::Item: CheckedNumber,
+{
+    /// Add all items, `what` describes the total in the error
+    ///
+    /// # Errors
+    ///
+    /// If the total does not fit in the item type.
+    fn checked_sum(mut self, what: &str) -> Result<Self::Item, SolveError> {
+        self.try_fold(Self::Item::ZERO, CheckedNumber::try_add)
+            .ok_or_else(|| SolveError::overflow(what))
+    }
+
+    /// Multiply all items, `what` describes the product in the error
+    ///
+    /// # Errors
+    ///
+    /// If the product does not fit in the item type.
+    fn checked_product(mut self, what: &str) -> Result<Self::Item, SolveError> {
+        self.try_fold(Self::Item::ONE, CheckedNumber::try_mul)
+            .ok_or_else(|| SolveError::overflow(what))
+    }
+}
+
+impl<I> CheckedIterator for I
+where
+    I: Iterator,
+    I::Item: CheckedNumber,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checked_sum_and_product() {
+        assert_eq!([1_u64, 2, 3].into_iter().checked_sum("total").unwrap(), 6);
+        assert_eq!(
+            [2_u64, 3, 4].into_iter().checked_product("total").unwrap(),
+            24
+        );
+        let err = [u64::MAX, 1].into_iter().checked_sum("total").unwrap_err();
+        assert_eq!(err.to_string(), "Overflow: total does not fit");
+        assert!(
+            [1_u128 << 64, 1 << 64]
+                .into_iter()
+                .checked_product("x")
+                .is_err()
+        );
+    }
+}
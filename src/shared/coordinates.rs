@@ -1,28 +1,55 @@
+use crate::shared::{Cells, SolveError};
 use std::collections::HashMap;
 use std::fmt::{Display, Formatter};
-use std::io::Lines;
-use std::ops::{Add, Range};
+use std::ops::Add;
+use std::str::Lines;
 
-/// Directions between 2D coordinates
+/// Directions between 2D coordinates, including the diagonals
 #[derive(Debug)]
 pub enum Direction {
     Up,
+    UpRight,
     Right,
+    DownRight,
     Down,
+    DownLeft,
     Left,
+    UpLeft,
+}
+
+impl Direction {
+    /// Change in coordinates for a single step in this direction
+    #[must_use]
+    pub fn offset(&self) -> RowCol {
+        match self {
+            Direction::Up => RowCol::new(-1, 0),
+            Direction::UpRight => RowCol::new(-1, 1),
+            Direction::Right => RowCol::new(0, 1),
+            Direction::DownRight => RowCol::new(1, 1),
+            Direction::Down => RowCol::new(1, 0),
+            Direction::DownLeft => RowCol::new(1, -1),
+            Direction::Left => RowCol::new(0, -1),
+            Direction::UpLeft => RowCol::new(-1, -1),
+        }
+    }
 }
 
 impl Display for Direction {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
             Direction::Up => write!(f, "UP"),
+            Direction::UpRight => write!(f, "UP_RIGHT"),
             Direction::Right => write!(f, "RIGHT"),
+            Direction::DownRight => write!(f, "DOWN_RIGHT"),
             Direction::Down => write!(f, "DOWN"),
+            Direction::DownLeft => write!(f, "DOWN_LEFT"),
             Direction::Left => write!(f, "LEFT"),
+            Direction::UpLeft => write!(f, "UP_LEFT"),
         }
     }
 }
 
+/// Orthogonal directions, clockwise from the top
 static DIRECTIONS: [Direction; 4] = [
     Direction::Up,
     Direction::Right,
@@ -30,12 +57,124 @@ static DIRECTIONS: [Direction; 4] = [
     Direction::Left,
 ];
 
-type I = i32;
+/// All directions including the diagonals, clockwise from the top
+static DIRECTIONS_8: [Direction; 8] = [
+    Direction::Up,
+    Direction::UpRight,
+    Direction::Right,
+    Direction::DownRight,
+    Direction::Down,
+    Direction::DownLeft,
+    Direction::Left,
+    Direction::UpLeft,
+];
+
+/// Set of locations that count as neighbours of a location
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Neighbourhood {
+    /// The 4 orthogonal neighbours
+    VonNeumann,
+    /// The 8 neighbours including the diagonals
+    Moore,
+    /// All locations within this Manhattan distance, excluding the location itself
+    Radius(I),
+    /// The 6 neighbours of a hexagonal grid in axial coordinates, where the diagonal from
+    /// bottom-left to top-right is adjacent too
+    Hex,
+}
+
+/// Steps to the orthogonal neighbours, clockwise from the top
+static OFFSETS_4: [RowCol; 4] = [
+    RowCol::new(-1, 0),
+    RowCol::new(0, 1),
+    RowCol::new(1, 0),
+    RowCol::new(0, -1),
+];
+
+/// Steps to all neighbours including the diagonals, clockwise from the top
+static OFFSETS_8: [RowCol; 8] = [
+    RowCol::new(-1, 0),
+    RowCol::new(-1, 1),
+    RowCol::new(0, 1),
+    RowCol::new(1, 1),
+    RowCol::new(1, 0),
+    RowCol::new(1, -1),
+    RowCol::new(0, -1),
+    RowCol::new(-1, -1),
+];
+
+/// Steps to the hexagonal neighbours in axial coordinates, clockwise from the top
+static OFFSETS_HEX: [RowCol; 6] = [
+    RowCol::new(-1, 0),
+    RowCol::new(-1, 1),
+    RowCol::new(0, 1),
+    RowCol::new(1, 0),
+    RowCol::new(1, -1),
+    RowCol::new(0, -1),
+];
+
+impl Neighbourhood {
+    /// Steps to each of the neighbours, without allocating
+    ///
+    /// Fixed neighbourhoods are in clockwise order from the top, a radius is row by row.
+    #[must_use]
+    pub fn offsets(&self) -> Offsets {
+        match self {
+            Neighbourhood::VonNeumann => Offsets::Fixed(OFFSETS_4.iter()),
+            Neighbourhood::Moore => Offsets::Fixed(OFFSETS_8.iter()),
+            Neighbourhood::Hex => Offsets::Fixed(OFFSETS_HEX.iter()),
+            Neighbourhood::Radius(radius) => Offsets::Radius {
+                radius: *radius,
+                row: -radius,
+                col: 0,
+            },
+        }
+    }
+}
+
+/// Iterator over the steps to the neighbours in a [`Neighbourhood`]
+#[derive(Debug, Clone)]
+pub enum Offsets {
+    Fixed(std::slice::Iter<'static, RowCol>),
+    /// Diamond of locations, generated row by row
+    Radius {
+        radius: I,
+        row: I,
+        col: I,
+    },
+}
+
+impl Iterator for Offsets {
+    type Item = RowCol;
+
+    fn next(&mut self) -> Option<RowCol> {
+        match self {
+            Offsets::Fixed(offsets) => offsets.next().copied(),
+            Offsets::Radius { radius, row, col } => loop {
+                if *row > *radius {
+                    return None;
+                }
+                if *col > *radius - row.abs() {
+                    *row += 1;
+                    *col = row.abs() - *radius;
+                    continue;
+                }
+                let offset = RowCol::new(*row, *col);
+                *col += 1;
+                if offset != RowCol::default() {
+                    return Some(offset); // Skip the location itself
+                }
+            },
+        }
+    }
+}
+
+pub(super) type I = i32;
 
 /// 2D coordinate through row and column
 ///
-/// (0,0) is always the top-left.
-#[derive(PartialEq, Eq, Hash, Debug, Copy, Clone)]
+/// (0,0) is always the top-left. Coordinates are ordered like reading, by row and then column.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Copy, Clone, Default)]
 pub struct RowCol {
     pub row: I,
     pub col: I,
@@ -44,40 +183,60 @@ pub struct RowCol {
 }
 
 impl RowCol {
-    pub fn new(row: I, col: I) -> Self {
+    /// Create a new coordinate
+    #[must_use]
+    pub const fn new(row: I, col: I) -> Self {
         Self { row, col }
     }
 
-    pub fn default() -> Self {
-        Self { row: 0, col: 0 }
-    }
-
     /// Get a new coordinate of a neighboring location
+    #[must_use]
     pub fn step(self, dir: &Direction) -> Self {
-        match dir {
-            Direction::Up => Self::new(self.row - 1, self.col),
-            Direction::Right => Self::new(self.row, self.col + 1),
-            Direction::Down => Self::new(self.row + 1, self.col),
-            Direction::Left => Self::new(self.row, self.col - 1),
-        }
+        self + dir.offset()
     }
 
     /// Loop over the 4 neighboring locations
     pub fn neighbours(&self) -> impl Iterator<Item = RowCol> {
         DIRECTIONS.iter().map(|dir| self.step(dir))
     }
+
+    /// Loop over the 8 neighboring locations, including the diagonals
+    pub fn neighbours_8(&self) -> impl Iterator<Item = RowCol> {
+        DIRECTIONS_8.iter().map(|dir| self.step(dir))
+    }
+
+    /// Loop over the neighboring locations in any neighbourhood
+    pub fn neighbours_in(self, neighbourhood: &Neighbourhood) -> impl Iterator<Item = RowCol> {
+        neighbourhood.offsets().map(move |offset| self + offset)
+    }
+
+    /// Number of orthogonal steps to another location
+    #[must_use]
+    pub fn manhattan_distance(self, other: Self) -> I {
+        (self.row - other.row).abs() + (self.col - other.col).abs()
+    }
+
+    /// Number of steps to another location when diagonal steps are allowed too
+    #[must_use]
+    pub fn chebyshev_distance(self, other: Self) -> I {
+        (self.row - other.row)
+            .abs()
+            .max((self.col - other.col).abs())
+    }
 }
 
-impl From<&str> for RowCol {
-    fn from(line: &str) -> Self {
-        let mut it = line.split(',');
+impl TryFrom<&str> for RowCol {
+    type Error = SolveError;
 
-        let part_to_num = |txt: Option<&str>| txt.unwrap().parse::<I>().unwrap();
+    /// Parse a coordinate from a string like "3,7"
+    fn try_from(line: &str) -> Result<Self, Self::Error> {
+        let parse_error = || SolveError::parse(format!("Expected `row,col`, got `{line}`"));
+        let (row, col) = line.split_once(',').ok_or_else(parse_error)?;
 
-        Self {
-            row: part_to_num(it.next()),
-            col: part_to_num(it.next()),
-        }
+        Ok(Self {
+            row: row.parse().map_err(|_| parse_error())?,
+            col: col.parse().map_err(|_| parse_error())?,
+        })
     }
 }
 
@@ -97,21 +256,34 @@ impl Add for RowCol {
 
 /// Helper object to make grid positions iterable
 #[derive(Debug)]
-pub struct GridIterator<'a> {
+pub struct GridIterator {
     loc: Option<RowCol>,
-    grid: &'a Grid,
+    rows: I,
+    cols: I,
 }
 
-impl Iterator for GridIterator<'_> {
+impl GridIterator {
+    /// Iterate over all positions of a grid of this size
+    pub(super) fn new(rows: I, cols: I) -> Self {
+        Self {
+            loc: None,
+            rows,
+            cols,
+        }
+    }
+}
+
+impl Iterator for GridIterator {
     type Item = RowCol;
 
     fn next(&mut self) -> Option<Self::Item> {
         match &mut self.loc {
+            None if self.rows <= 0 || self.cols <= 0 => return None,
             None => self.loc = Some(RowCol::default()),
             Some(loc) => {
-                if loc.col < self.grid.cols - 1 {
+                if loc.col < self.cols - 1 {
                     loc.col += 1;
-                } else if loc.row < self.grid.rows - 1 {
+                } else if loc.row < self.rows - 1 {
                     loc.col = 0;
                     loc.row += 1;
                 } else {
@@ -125,15 +297,18 @@ impl Iterator for GridIterator<'_> {
 }
 
 /// Range of rows and columns in 2D, with or without items
-#[derive(Debug)]
+///
+/// Only the filled locations are stored, see [`DenseGrid`](crate::shared::DenseGrid) for a dense one.
+#[derive(Debug, Clone)]
 pub struct Grid {
     pub rows: i32,
     pub cols: i32,
     pub items: HashMap<RowCol, char>, // Grid items are marked only with a single character
 }
 
-#[allow(dead_code)]
 impl Grid {
+    /// Create an empty grid of a given size
+    #[must_use]
     pub fn new(rows: I, cols: I) -> Self {
         Self {
             rows,
@@ -142,27 +317,13 @@ impl Grid {
         }
     }
 
-    pub fn default() -> Self {
-        Self::new(0, 0)
-    }
-
-    pub fn range_rows(&self) -> Range<I> {
-        0..self.rows
-    }
-
-    pub fn range_cols(&self) -> Range<I> {
-        0..self.cols
-    }
-
-    /// Iterable over all coordinates in this grid, left-to-right and then top-to-bottom
-    pub fn range(&self) -> GridIterator<'_> {
-        GridIterator {
-            loc: None,
-            grid: self,
-        }
-    }
-
     /// Insert a new item into the grid
+    ///
+    /// The grid is expanded when the location lies outside of it.
+    ///
+    /// # Panics
+    ///
+    /// If the location is already filled.
     pub fn add_item(&mut self, loc: RowCol, symbol: char) {
         assert!(
             !self.items.contains_key(&loc),
@@ -183,6 +344,10 @@ impl Grid {
     }
 
     /// Return the first item with a given symbol
+    ///
+    /// # Errors
+    ///
+    /// If no item has this symbol.
     pub fn get_item_by_symbol(&self, symbol: char) -> Result<RowCol, String> {
         for (&loc, &c) in &self.items {
             if c == symbol {
@@ -233,12 +398,34 @@ impl Grid {
     }
 }
 
-impl<T: std::io::BufRead> From<Lines<T>> for Grid {
-    fn from(lines: Lines<T>) -> Self {
+impl Cells for Grid {
+    type Cell = char;
+
+    fn rows(&self) -> I {
+        self.rows
+    }
+
+    fn cols(&self) -> I {
+        self.cols
+    }
+
+    fn get(&self, loc: RowCol) -> Option<&char> {
+        self.items.get(&loc)
+    }
+}
+
+impl Default for Grid {
+    fn default() -> Self {
+        Self::new(0, 0)
+    }
+}
+
+impl From<Lines<'_>> for Grid {
+    fn from(lines: Lines) -> Self {
         let mut grid = Grid::default();
 
         for line in lines {
-            grid.add_row_from_text(&line.unwrap());
+            grid.add_row_from_text(line);
         }
 
         grid
@@ -249,6 +436,7 @@ impl<T: std::io::BufRead> From<Lines<T>> for Grid {
 mod tests_row_col {
     use super::*;
 
+    #[allow(clippy::field_reassign_with_default)]
     #[test]
     fn test_row_col() {
         let p1 = RowCol { row: 1, col: 5 };
@@ -278,10 +466,54 @@ mod tests_row_col {
         );
     }
 
+    #[test]
+    fn test_row_col_neighbourhoods() {
+        let loc = RowCol::new(3, 1);
+        let list: Vec<RowCol> = loc.neighbours_8().collect();
+        assert_eq!(list.len(), 8);
+        assert_eq!(list[1], RowCol::new(2, 2));
+        assert_eq!(list[7], RowCol::new(2, 0));
+        assert!(loc.neighbours().all(|n| list.contains(&n)));
+
+        let moore: Vec<RowCol> = loc.neighbours_in(&Neighbourhood::Moore).collect();
+        assert_eq!(moore, list);
+        assert_eq!(Neighbourhood::VonNeumann.offsets().count(), 4);
+        assert_eq!(Neighbourhood::Hex.offsets().count(), 6);
+        assert_eq!(Neighbourhood::Radius(0).offsets().count(), 0);
+        let radius: Vec<RowCol> = Neighbourhood::Radius(2).offsets().collect();
+        assert_eq!(radius.len(), 12);
+        assert_eq!(radius[0], RowCol::new(-2, 0));
+        assert_eq!(
+            Neighbourhood::Radius(1).offsets().collect::<Vec<_>>(),
+            vec![
+                RowCol::new(-1, 0),
+                RowCol::new(0, -1),
+                RowCol::new(0, 1),
+                RowCol::new(1, 0)
+            ]
+        );
+        assert!(radius.iter().all(|o| o.row.abs() + o.col.abs() <= 2));
+    }
+
+    #[test]
+    fn test_row_col_from_str() {
+        assert_eq!(RowCol::try_from("3,7").unwrap(), RowCol::new(3, 7));
+        assert!(RowCol::try_from("3;7").is_err());
+        assert!(RowCol::try_from("3,x").is_err());
+    }
+
     #[test]
     fn test_row_col_sum() {
         assert_eq!(RowCol::new(1, 2) + RowCol::new(3, 4), RowCol::new(4, 6));
     }
+
+    #[test]
+    fn test_row_col_distance() {
+        let (p1, p2) = (RowCol::new(1, 5), RowCol::new(4, 3));
+        assert_eq!(p1.manhattan_distance(p2), 5);
+        assert_eq!(p1.chebyshev_distance(p2), 3);
+        assert!(p1 < p2 && RowCol::new(1, 6) > p1);
+    }
 }
 
 #[cfg(test)]
@@ -306,6 +538,7 @@ mod tests_grid {
             list.push(loc);
         }
         assert_eq!(list, expected);
+        assert_eq!(Grid::new(0, 3).range().count(), 0);
     }
 
     #[test]
@@ -0,0 +1,153 @@
+use std::fmt::{Display, Formatter};
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Reasons for a daily solver to fail
+#[derive(Debug)]
+pub enum SolveError {
+    /// Input could not be read
+    Io {
+        file: Option<PathBuf>,
+        source: io::Error,
+    },
+    /// Input could not be parsed, line and column are 1-based
+    Parse {
+        file: Option<PathBuf>,
+        line: Option<usize>,
+        column: Option<usize>,
+        message: String,
+    },
+    /// Input was parsed fine, but no answer could be found for it
+    Unsolvable(String),
+    /// Answer (or an intermediate value) is too big for its type
+    Overflow(String),
+}
+
+impl SolveError {
+    /// Create a parse error, the location can be added later
+    pub fn parse(message: impl Into<String>) -> Self {
+        SolveError::Parse {
+            file: None,
+            line: None,
+            column: None,
+            message: message.into(),
+        }
+    }
+
+    /// Create an overflow error, `what` describes the value that does not fit
+    #[must_use]
+    pub fn overflow(what: &str) -> Self {
+        SolveError::Overflow(format!("{what} does not fit"))
+    }
+
+    /// Set the line number of a parse error (1-based)
+    #[must_use]
+    pub fn on_line(mut self, number: usize) -> Self {
+        if let SolveError::Parse { line, .. } = &mut self {
+            *line = Some(number);
+        }
+        self
+    }
+
+    /// Set the column number of a parse error (1-based)
+    #[must_use]
+    pub fn at_column(mut self, number: usize) -> Self {
+        if let SolveError::Parse { column, .. } = &mut self {
+            *column = Some(number);
+        }
+        self
+    }
+
+    /// Set the file the error originates from
+    #[must_use]
+    pub fn with_file(mut self, path: &Path) -> Self {
+        if let SolveError::Io { file, .. } | SolveError::Parse { file, .. } = &mut self {
+            *file = Some(path.to_path_buf());
+        }
+        self
+    }
+}
+
+impl Display for SolveError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SolveError::Io { file, source } => {
+                if let Some(file) = file {
+                    write!(f, "{}: ", file.display())?;
+                }
+                write!(f, "{source}")
+            }
+            SolveError::Parse {
+                file,
+                line,
+                column,
+                message,
+            } => {
+                let mut location: Vec<String> = Vec::new();
+                if let Some(file) = file {
+                    location.push(file.display().to_string());
+                }
+                if let Some(line) = line {
+                    location.push(format!("line {line}"));
+                }
+                if let Some(column) = column {
+                    location.push(format!("column {column}"));
+                }
+                if !location.is_empty() {
+                    write!(f, "{}: ", location.join(", "))?;
+                }
+                write!(f, "{message}")
+            }
+            SolveError::Unsolvable(message) => write!(f, "No solution found: {message}"),
+            SolveError::Overflow(message) => write!(f, "Overflow: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for SolveError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            SolveError::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for SolveError {
+    fn from(source: io::Error) -> Self {
+        SolveError::Io { file: None, source }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_parse_error() {
+        let err = SolveError::parse("expected `a-b` range");
+        assert_eq!(err.to_string(), "expected `a-b` range");
+
+        let err = err.on_line(12).with_file(Path::new("inputs/day_05.txt"));
+        assert_eq!(
+            err.to_string(),
+            "inputs/day_05.txt, line 12: expected `a-b` range"
+        );
+
+        let err = err.at_column(3);
+        assert_eq!(
+            err.to_string(),
+            "inputs/day_05.txt, line 12, column 3: expected `a-b` range"
+        );
+    }
+
+    #[test]
+    fn display_other_errors() {
+        let err = SolveError::from(io::Error::new(io::ErrorKind::NotFound, "not found"))
+            .with_file(Path::new("day_01.txt"));
+        assert_eq!(err.to_string(), "day_01.txt: not found");
+
+        let err = SolveError::Unsolvable(String::from("circuit never closes"));
+        assert_eq!(err.to_string(), "No solution found: circuit never closes");
+    }
+}
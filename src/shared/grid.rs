@@ -0,0 +1,386 @@
+use crate::shared::coordinates::I;
+use crate::shared::{GridIterator, Neighbourhood, RowCol, SolveError};
+use std::fmt::{Display, Formatter};
+use std::ops::{Index, IndexMut, Range};
+use std::str::Lines;
+
+/// Read access to a rectangular grid of cells, however they are stored
+pub trait Cells {
+    /// Content of a single cell
+    type Cell;
+
+    /// Number of rows
+    fn rows(&self) -> I;
+
+    /// Number of columns
+    fn cols(&self) -> I;
+
+    /// Get the cell at a location, `None` outside the grid (or for an empty cell of a sparse grid)
+    fn get(&self, loc: RowCol) -> Option<&Self::Cell>;
+
+    /// Whether a location lies within the grid
+    fn contains(&self, loc: RowCol) -> bool {
+        self.range_rows().contains(&loc.row) && self.range_cols().contains(&loc.col)
+    }
+
+    /// Range of all row indices
+    fn range_rows(&self) -> Range<I> {
+        0..self.rows()
+    }
+
+    /// Range of all column indices
+    fn range_cols(&self) -> Range<I> {
+        0..self.cols()
+    }
+
+    /// Iterable over all coordinates in this grid, left-to-right and then top-to-bottom
+    fn range(&self) -> GridIterator {
+        GridIterator::new(self.rows(), self.cols())
+    }
+
+    /// Neighbours of a location that lie within the grid
+    fn neighbours_within(
+        &self,
+        loc: RowCol,
+        neighbourhood: &Neighbourhood,
+    ) -> impl Iterator<Item = RowCol> {
+        loc.neighbours_in(neighbourhood)
+            .filter(|&other| self.contains(other))
+    }
+
+    /// Cells around a location, skipping those outside the grid (or empty in a sparse grid)
+    fn neighbouring_cells(
+        &self,
+        loc: RowCol,
+        neighbourhood: &Neighbourhood,
+    ) -> impl Iterator<Item = (RowCol, &Self::Cell)> {
+        loc.neighbours_in(neighbourhood)
+            .filter_map(|other| self.get(other).map(|cell| (other, cell)))
+    }
+
+    /// Count the cells around a location for which `predicate` holds
+    fn count_neighbours_matching(
+        &self,
+        loc: RowCol,
+        neighbourhood: &Neighbourhood,
+        predicate: impl Fn(&Self::Cell) -> bool,
+    ) -> usize {
+        self.neighbouring_cells(loc, neighbourhood)
+            .filter(|(_, cell)| predicate(cell))
+            .count()
+    }
+}
+
+/// Rectangular grid with a value in every cell, stored row after row
+///
+/// Grids are ordered by their size first and then by their cells, in reading order.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct DenseGrid<T> {
+    rows: I,
+    cols: I,
+    cells: Vec<T>, // `rows * cols` cells, top row first
+}
+
+impl<T> DenseGrid<T> {
+    /// Create a grid of a given size with every cell set to `value`
+    ///
+    /// # Panics
+    ///
+    /// If a size is negative.
+    #[must_use]
+    pub fn new(rows: I, cols: I, value: T) -> Self
+    where
+        T: Clone,
+    {
+        let count = usize::try_from(rows).unwrap() * usize::try_from(cols).unwrap();
+        Self {
+            rows,
+            cols,
+            cells: vec![value; count],
+        }
+    }
+
+    /// Create a grid of a given size, getting the value of every cell from its location
+    ///
+    /// # Panics
+    ///
+    /// If a size is negative.
+    pub fn from_fn(rows: I, cols: I, func: impl FnMut(RowCol) -> T) -> Self {
+        assert!(
+            rows >= 0 && cols >= 0,
+            "Grid size {rows}x{cols} is negative"
+        );
+        Self {
+            rows,
+            cols,
+            cells: GridIterator::new(rows, cols).map(func).collect(),
+        }
+    }
+
+    /// Create a grid from its rows, which must all be equally long
+    ///
+    /// # Errors
+    ///
+    /// If a row differs in length from the first one, the line number is that of the row.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Self, SolveError> {
+        let too_big = || SolveError::parse("Grid is too big");
+        let cols = rows.first().map_or(0, Vec::len);
+        let count = I::try_from(rows.len()).map_err(|_| too_big())?;
+        let mut cells = Vec::with_capacity(rows.len() * cols);
+        for (idx, row) in rows.into_iter().enumerate() {
+            if row.len() != cols {
+                return Err(SolveError::parse(format!(
+                    "Expected {cols} columns like the first row, got {}",
+                    row.len()
+                ))
+                .on_line(idx + 1));
+            }
+            cells.extend(row);
+        }
+
+        Ok(Self {
+            rows: count,
+            cols: I::try_from(cols).map_err(|_| too_big())?,
+            cells,
+        })
+    }
+
+    /// Parse lines of symbols, like from an input.txt, turning each symbol into a cell
+    ///
+    /// # Errors
+    ///
+    /// If `mapping` gives `None` for a symbol, or the lines differ in length.
+    pub fn parse(
+        lines: Lines,
+        mut mapping: impl FnMut(char) -> Option<T>,
+    ) -> Result<Self, SolveError> {
+        let mut rows = Vec::new();
+        for (idx, line) in lines.enumerate() {
+            let mut row = Vec::with_capacity(line.len());
+            for (col, symbol) in line.chars().enumerate() {
+                let cell = mapping(symbol).ok_or_else(|| {
+                    SolveError::parse(format!("Unexpected symbol `{symbol}`"))
+                        .on_line(idx + 1)
+                        .at_column(col + 1)
+                })?;
+                row.push(cell);
+            }
+            rows.push(row);
+        }
+        Self::from_rows(rows)
+    }
+
+    /// Position of a location in `cells`, `None` outside the grid
+    fn offset(&self, loc: RowCol) -> Option<usize> {
+        if !self.contains(loc) {
+            return None;
+        }
+        usize::try_from(loc.row * self.cols + loc.col).ok()
+    }
+
+    /// Get the cell at a location to change it, `None` outside the grid
+    pub fn get_mut(&mut self, loc: RowCol) -> Option<&mut T> {
+        let offset = self.offset(loc)?;
+        self.cells.get_mut(offset)
+    }
+
+    /// All cells of a row, left to right
+    ///
+    /// # Panics
+    ///
+    /// If the row lies outside the grid.
+    #[must_use]
+    pub fn row(&self, row: I) -> &[T] {
+        assert!(
+            self.range_rows().contains(&row),
+            "Row {row} is outside the grid"
+        );
+        let width = self.cols.unsigned_abs() as usize;
+        let start = row.unsigned_abs() as usize * width;
+        &self.cells[start..start + width]
+    }
+
+    /// All cells of a column, top to bottom
+    ///
+    /// # Panics
+    ///
+    /// If the column lies outside the grid.
+    pub fn col(&self, col: I) -> impl Iterator<Item = &T> {
+        assert!(
+            self.range_cols().contains(&col),
+            "Column {col} is outside the grid"
+        );
+        self.cells[col.unsigned_abs() as usize..]
+            .iter()
+            .step_by(self.cols.unsigned_abs() as usize)
+    }
+
+    /// Loop over all locations with their cell, left-to-right and then top-to-bottom
+    pub fn iter(&self) -> impl Iterator<Item = (RowCol, &T)> {
+        self.range().zip(self.cells.iter())
+    }
+
+    /// Create a grid of the same size with every cell converted
+    #[must_use]
+    pub fn map<U>(&self, func: impl FnMut(&T) -> U) -> DenseGrid<U> {
+        DenseGrid {
+            rows: self.rows,
+            cols: self.cols,
+            cells: self.cells.iter().map(func).collect(),
+        }
+    }
+}
+
+impl<T> Cells for DenseGrid<T> {
+    type Cell = T;
+
+    fn rows(&self) -> I {
+        self.rows
+    }
+
+    fn cols(&self) -> I {
+        self.cols
+    }
+
+    fn get(&self, loc: RowCol) -> Option<&T> {
+        self.cells.get(self.offset(loc)?)
+    }
+}
+
+impl<T> Index<RowCol> for DenseGrid<T> {
+    type Output = T;
+
+    fn index(&self, loc: RowCol) -> &T {
+        self.get(loc)
+            .unwrap_or_else(|| panic!("Location {loc} is outside the grid"))
+    }
+}
+
+impl<T> IndexMut<RowCol> for DenseGrid<T> {
+    fn index_mut(&mut self, loc: RowCol) -> &mut T {
+        self.get_mut(loc)
+            .unwrap_or_else(|| panic!("Location {loc} is outside the grid"))
+    }
+}
+
+impl<T: From<char> + Clone> From<Lines<'_>> for DenseGrid<T> {
+    /// Convert every symbol with `T::from`, shorter lines are padded with `.` (empty space)
+    fn from(lines: Lines) -> Self {
+        let mut rows: Vec<Vec<T>> = lines
+            .map(|line| line.chars().map(T::from).collect())
+            .collect();
+        let cols = rows.iter().map(Vec::len).max().unwrap_or(0);
+        for row in &mut rows {
+            row.resize(cols, T::from('.'));
+        }
+        Self::from_rows(rows).expect("Rows are padded to the same length")
+    }
+}
+
+impl<T: Display> Display for DenseGrid<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for row in self.range_rows() {
+            for cell in self.row(row) {
+                write!(f, "{cell}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::Grid;
+
+    #[test]
+    fn test_grid_parse() {
+        let grid = DenseGrid::parse("#.#\n..#".lines(), |c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        })
+        .unwrap();
+        assert_eq!((grid.rows(), grid.cols()), (2, 3));
+        assert_eq!(grid.get(RowCol::new(0, 2)), Some(&true));
+        assert_eq!(grid.get(RowCol::new(1, 0)), Some(&false));
+        assert_eq!(grid.get(RowCol::new(2, 0)), None);
+        assert_eq!(grid.get(RowCol::new(0, -1)), None);
+        assert_eq!(grid.iter().filter(|(_, cell)| **cell).count(), 3);
+
+        let err = DenseGrid::parse("#.\n.x".lines(), |c| (c != 'x').then_some(c)).unwrap_err();
+        assert_eq!(err.to_string(), "line 2, column 2: Unexpected symbol `x`");
+        assert!(DenseGrid::parse("#.\n.".lines(), Some).is_err());
+    }
+
+    #[test]
+    fn test_grid_rows_and_cols() {
+        let mut grid: DenseGrid<char> = DenseGrid::from("abc\nde".lines());
+        assert_eq!(grid.row(1), &['d', 'e', '.']);
+        assert_eq!(grid.col(1).collect::<String>(), "be");
+        grid[RowCol::new(1, 2)] = 'f';
+        assert_eq!(grid.to_string(), "abc\ndef\n");
+
+        let sizes = grid.map(|c| u32::from(*c) - u32::from('a'));
+        assert_eq!(sizes[RowCol::new(1, 2)], 5);
+        assert_eq!(DenseGrid::new(2, 2, 0).range().count(), 4);
+    }
+
+    #[test]
+    fn test_grid_zero_width() {
+        let grid: DenseGrid<char> = DenseGrid::from("\n\n".lines());
+        assert_eq!((grid.rows(), grid.cols()), (2, 0));
+        assert!(grid.row(1).is_empty());
+        assert_eq!(grid.to_string(), "\n\n");
+        assert_eq!(DenseGrid::new(3, 0, '.').to_string(), "\n\n\n");
+    }
+
+    #[test]
+    fn test_grid_neighbourhoods() {
+        let grid: DenseGrid<char> = DenseGrid::from("ab.\n.c@\n".lines());
+        let corner = RowCol::new(0, 0);
+        assert_eq!(
+            grid.neighbours_within(corner, &Neighbourhood::Moore)
+                .count(),
+            3
+        );
+        assert_eq!(
+            grid.neighbours_within(corner, &Neighbourhood::VonNeumann)
+                .collect::<Vec<_>>(),
+            vec![RowCol::new(0, 1), RowCol::new(1, 0)]
+        );
+        let centre = RowCol::new(1, 1);
+        let letters = |c: &char| c.is_ascii_lowercase();
+        assert_eq!(
+            grid.count_neighbours_matching(centre, &Neighbourhood::Moore, letters),
+            2
+        );
+        assert_eq!(
+            grid.count_neighbours_matching(centre, &Neighbourhood::Hex, letters),
+            1
+        );
+        assert_eq!(
+            grid.neighbouring_cells(centre, &Neighbourhood::Radius(2))
+                .count(),
+            5
+        );
+
+        let sparse = Grid::from("ab.\n.c@\n".lines());
+        assert_eq!(
+            sparse
+                .neighbouring_cells(centre, &Neighbourhood::Moore)
+                .count(),
+            3
+        );
+    }
+
+    #[test]
+    fn test_sparse_cells() {
+        let grid = Grid::from("a.\n.b".lines());
+        assert_eq!(grid.get(RowCol::new(1, 1)), Some(&'b'));
+        assert_eq!(grid.get(RowCol::new(0, 1)), None);
+        assert!(grid.contains(RowCol::new(0, 1)));
+        assert!(!grid.contains(RowCol::new(2, 1)));
+    }
+}
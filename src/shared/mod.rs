@@ -1,5 +1,27 @@
+//! Helpers shared between the daily solvers
+
+mod checked;
 mod coordinates;
+mod error;
+mod grid;
+mod outcome;
+mod parallel;
+mod params;
+mod regions;
+mod search;
 mod solution;
+mod trace;
+mod transform;
 
-pub use coordinates::{Grid, RowCol};
-pub use solution::{Outcome, Solution};
+pub use checked::{CheckedIterator, CheckedNumber};
+pub use coordinates::{Direction, Grid, GridIterator, Neighbourhood, Offsets, RowCol};
+pub use error::SolveError;
+pub use grid::{Cells, DenseGrid};
+pub use outcome::Outcome;
+pub use parallel::Execution;
+pub use params::{ParamSpec, Params};
+pub use regions::{Region, Regions};
+pub use search::{Distances, Search};
+pub use solution::{ParsedInput, Solution, SolveResult, Solver, read_input};
+pub use trace::{Level, Trace};
+pub use transform::Symmetry;
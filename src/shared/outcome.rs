@@ -0,0 +1,160 @@
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+
+/// Possible outcomes for a daily solver: a number, a string or a block of text
+///
+/// In JSON it becomes `{"type": "U64", "value": 123}`, a `Grid` has a list of lines as value.
+/// Numbers compare by value, whatever their variant, so `U64(5)` equals `U128(5)`, and are ordered.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", content = "value")]
+pub enum Outcome {
+    U64(u64),
+    I64(i64),
+    U128(u128),
+    Text(String),
+    Grid(Vec<String>), // Multi-line answer, like letters drawn in a grid
+}
+
+impl Outcome {
+    /// Interpret an answer written as text, picking the narrowest variant that fits
+    ///
+    /// Whole numbers become `U64`, `I64` or `U128`, multiple lines become a `Grid`.
+    #[must_use]
+    pub fn from_text(txt: &str) -> Self {
+        if let Ok(n) = txt.parse::<u64>() {
+            Outcome::U64(n)
+        } else if let Ok(n) = txt.parse::<i64>() {
+            Outcome::I64(n)
+        } else if let Ok(n) = txt.parse::<u128>() {
+            Outcome::U128(n)
+        } else if txt.trim_end().contains('\n') {
+            Outcome::Grid(txt.lines().map(String::from).collect())
+        } else {
+            Outcome::Text(txt.to_string())
+        }
+    }
+
+    /// Name of the variant, as used for `type` in JSON
+    #[must_use]
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Outcome::U64(_) => "U64",
+            Outcome::I64(_) => "I64",
+            Outcome::U128(_) => "U128",
+            Outcome::Text(_) => "Text",
+            Outcome::Grid(_) => "Grid",
+        }
+    }
+
+    /// Numeric value as sign (`true` for negative) and magnitude, `None` for text
+    fn as_signed_magnitude(&self) -> Option<(bool, u128)> {
+        match self {
+            Outcome::U64(n) => Some((false, u128::from(*n))),
+            Outcome::I64(n) => Some((*n < 0, u128::from(n.unsigned_abs()))),
+            Outcome::U128(n) => Some((false, *n)),
+            Outcome::Text(_) | Outcome::Grid(_) => None,
+        }
+    }
+}
+
+impl PartialEq for Outcome {
+    fn eq(&self, other: &Self) -> bool {
+        match (self.as_signed_magnitude(), other.as_signed_magnitude()) {
+            (Some(a), Some(b)) => a == b,
+            (None, None) => self.to_string() == other.to_string(),
+            _ => false,
+        }
+    }
+}
+
+/// Order numbers by value, text only equals itself
+impl PartialOrd for Outcome {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match (self.as_signed_magnitude(), other.as_signed_magnitude()) {
+            (Some((a_negative, a)), Some((b_negative, b))) => {
+                Some(match (a_negative, b_negative) {
+                    (false, false) => a.cmp(&b),
+                    (true, true) => b.cmp(&a),
+                    (true, false) => Ordering::Less,
+                    (false, true) => Ordering::Greater,
+                })
+            }
+            _ => (self == other).then_some(Ordering::Equal),
+        }
+    }
+}
+
+/// Implement string conversion for our general `Outcome` enum
+impl Display for Outcome {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            Outcome::U64(n) => write!(f, "{n}"),
+            Outcome::I64(n) => write!(f, "{n}"),
+            Outcome::U128(n) => write!(f, "{n}"),
+            Outcome::Text(txt) => write!(f, "{txt}"),
+            Outcome::Grid(lines) => write!(f, "{}", lines.join("\n")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn outcome_equality() {
+        assert_eq!(Outcome::U64(5), Outcome::U128(5));
+        assert_eq!(Outcome::I64(5), Outcome::U64(5));
+        assert_ne!(Outcome::I64(-5), Outcome::U64(5));
+        assert_ne!(Outcome::U64(5), Outcome::Text(String::from("5")));
+        assert_eq!(
+            Outcome::Grid(vec![String::from("#."), String::from(".#")]),
+            Outcome::Text(String::from("#.\n.#"))
+        );
+    }
+
+    #[test]
+    fn outcome_order() {
+        assert!(Outcome::U64(5) < Outcome::U128(6));
+        assert!(Outcome::I64(-7) < Outcome::I64(-6));
+        assert!(Outcome::I64(-1) < Outcome::U64(0));
+        assert!(Outcome::U128(u128::MAX) > Outcome::I64(i64::MAX));
+        let text = Outcome::Text(String::from("abc"));
+        assert_eq!(text.partial_cmp(&text.clone()), Some(Ordering::Equal));
+        assert_eq!(text.partial_cmp(&Outcome::U64(1)), None);
+    }
+
+    #[test]
+    fn outcome_from_text() {
+        assert_eq!(Outcome::from_text("12").kind(), "U64");
+        assert_eq!(Outcome::from_text("-12").kind(), "I64");
+        assert_eq!(
+            Outcome::from_text("18446744073709551616"),
+            Outcome::U128(1 << 64)
+        );
+        assert_eq!(Outcome::from_text("abc").kind(), "Text");
+        assert_eq!(
+            Outcome::from_text("#.\n.#\n"),
+            Outcome::Grid(vec![String::from("#."), String::from(".#")])
+        );
+    }
+
+    #[test]
+    fn outcome_display_and_json() {
+        let grid = Outcome::Grid(vec![String::from("#."), String::from(".#")]);
+        assert_eq!(grid.to_string(), "#.\n.#");
+        assert_eq!(
+            serde_json::to_string(&grid).unwrap(),
+            r##"{"type":"Grid","value":["#.",".#"]}"##
+        );
+        assert_eq!(
+            serde_json::to_string(&Outcome::I64(-3)).unwrap(),
+            r#"{"type":"I64","value":-3}"#
+        );
+        assert_eq!(
+            serde_json::to_string(&Outcome::U128(u128::MAX)).unwrap(),
+            format!(r#"{{"type":"U128","value":{}}}"#, u128::MAX)
+        );
+    }
+}
@@ -0,0 +1,87 @@
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use std::cmp::Ordering;
+
+/// How to run the data-parallel loops of a solver
+///
+/// `Parallel` only spreads work over threads with the `parallel` feature, and is the same as
+/// `Sequential` otherwise. Both give identical results, such that tests can compare them.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Execution {
+    Sequential,
+    Parallel,
+}
+
+impl Execution {
+    /// Parallel when the `parallel` feature is enabled
+    pub const DEFAULT: Self = if cfg!(feature = "parallel") {
+        Execution::Parallel
+    } else {
+        Execution::Sequential
+    };
+
+    /// Apply `func` to every item, results are in the order of the items
+    pub fn map<T, R, F>(self, items: &[T], func: F) -> Vec<R>
+    where
+        T: Sync,
+        R: Send,
+        F: Fn(&T) -> R + Sync + Send,
+    {
+        match self {
+            #[cfg(feature = "parallel")]
+            Execution::Parallel => items.par_iter().map(func).collect(),
+            _ => items.iter().map(func).collect(),
+        }
+    }
+
+    /// Apply `func` to every index in `0..count`, results are in the order of the indices
+    pub fn map_indices<R, F>(self, count: usize, func: F) -> Vec<R>
+    where
+        R: Send,
+        F: Fn(usize) -> R + Sync + Send,
+    {
+        match self {
+            #[cfg(feature = "parallel")]
+            Execution::Parallel => (0..count).into_par_iter().map(func).collect(),
+            _ => (0..count).map(func).collect(),
+        }
+    }
+
+    /// Stable sort, equal items keep their order
+    pub fn sort_by<T, F>(self, items: &mut [T], compare: F)
+    where
+        T: Send,
+        F: Fn(&T, &T) -> Ordering + Sync,
+    {
+        match self {
+            #[cfg(feature = "parallel")]
+            Execution::Parallel => items.par_sort_by(compare),
+            _ => items.sort_by(compare),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn execution_keeps_order() {
+        let items: Vec<u64> = (0..1_000).rev().collect();
+        let square = |n: &u64| n * n;
+        assert_eq!(
+            Execution::Parallel.map(&items, square),
+            Execution::Sequential.map(&items, square)
+        );
+        assert_eq!(
+            Execution::Parallel.map_indices(100, |i| i + 1),
+            (1..=100).collect::<Vec<usize>>()
+        );
+
+        let mut pairs: Vec<(usize, usize)> = (0..1_000).map(|i| (i % 7, i)).collect();
+        let mut expected = pairs.clone();
+        Execution::Parallel.sort_by(&mut pairs, |a, b| a.0.cmp(&b.0));
+        Execution::Sequential.sort_by(&mut expected, |a, b| a.0.cmp(&b.0));
+        assert_eq!(pairs, expected);
+    }
+}
@@ -0,0 +1,167 @@
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+
+/// Declaration of a tunable parameter of a solver
+///
+/// Values are whole numbers, limited to `min..=max` such that solvers can convert them safely.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ParamSpec {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub default: u64,
+    pub min: u64,
+    pub max: u64,
+    pub below: Option<&'static str>, // Name of another parameter the value must be less than
+}
+
+impl Display for ParamSpec {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "{}={} ({}..={}): {}",
+            self.name, self.default, self.min, self.max, self.description
+        )
+    }
+}
+
+/// Values for the parameters of a solver, defaults unless overridden
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Params {
+    values: HashMap<&'static str, u64>,
+}
+
+impl Params {
+    /// Take the default value of each parameter
+    #[must_use]
+    pub fn defaults(specs: &[ParamSpec]) -> Self {
+        Self {
+            values: specs.iter().map(|spec| (spec.name, spec.default)).collect(),
+        }
+    }
+
+    /// Apply assignments like `limit=10` on top of the defaults
+    ///
+    /// # Errors
+    ///
+    /// If an assignment is malformed, names an unknown parameter or has an invalid value.
+    pub fn resolve(specs: &[ParamSpec], assignments: &[String]) -> Result<Self, String> {
+        let mut params = Self::defaults(specs);
+        for assignment in assignments {
+            let Some((name, value)) = assignment.split_once('=') else {
+                return Err(format!("Expected `name=value`, got `{assignment}`"));
+            };
+            let Some(spec) = specs.iter().find(|spec| spec.name == name.trim()) else {
+                let names: Vec<&str> = specs.iter().map(|spec| spec.name).collect();
+                return Err(if names.is_empty() {
+                    format!("Unknown parameter `{name}`, this day has no parameters")
+                } else {
+                    format!(
+                        "Unknown parameter `{name}`, expected one of: {}",
+                        names.join(", ")
+                    )
+                });
+            };
+            let value: u64 = value.trim().parse().map_err(|_| {
+                format!("Expected a whole number for `{}`, got `{value}`", spec.name)
+            })?;
+            if !(spec.min..=spec.max).contains(&value) {
+                return Err(format!(
+                    "Value for `{}` must be in {}..={}, got {value}",
+                    spec.name, spec.min, spec.max
+                ));
+            }
+            params.values.insert(spec.name, value);
+        }
+        params.check_below(specs)?;
+        Ok(params)
+    }
+
+    /// Make sure values are less than the parameters they must stay below
+    fn check_below(&self, specs: &[ParamSpec]) -> Result<(), String> {
+        for spec in specs {
+            let Some(other) = spec.below else {
+                continue;
+            };
+            let (value, limit) = (self.values[spec.name], self.values[other]);
+            if value >= limit {
+                return Err(format!(
+                    "Value for `{}` must be below `{other}` ({limit}), got {value}",
+                    spec.name
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Get the value of a declared parameter, converted to the type the solver needs
+    ///
+    /// # Panics
+    ///
+    /// If the parameter was not declared, or its range does not fit in `T`.
+    #[must_use]
+    pub fn get<T: TryFrom<u64>>(&self, name: &str) -> T {
+        let value = *self
+            .values
+            .get(name)
+            .unwrap_or_else(|| panic!("Parameter `{name}` is not declared"));
+        T::try_from(value)
+            .unwrap_or_else(|_| panic!("Parameter `{name}` does not fit, check its range"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPECS: [ParamSpec; 2] = [
+        ParamSpec {
+            name: "limit",
+            description: "Number of connections",
+            default: 1_000,
+            min: 1,
+            max: 1_000_000,
+            below: None,
+        },
+        ParamSpec {
+            name: "start",
+            description: "Starting position",
+            default: 50,
+            min: 0,
+            max: 99,
+            below: Some("limit"),
+        },
+    ];
+
+    #[test]
+    fn params_resolve() {
+        let params = Params::resolve(&SPECS, &[String::from("limit=60")]).unwrap();
+        assert_eq!(params.get::<usize>("limit"), 60);
+        assert_eq!(params.get::<i32>("start"), 50);
+        assert_eq!(
+            Params::resolve(&SPECS, &[]).unwrap(),
+            Params::defaults(&SPECS)
+        );
+    }
+
+    #[test]
+    fn params_resolve_invalid() {
+        let resolve = |arg: &str| Params::resolve(&SPECS, &[String::from(arg)]).unwrap_err();
+        assert_eq!(resolve("limit"), "Expected `name=value`, got `limit`");
+        assert_eq!(
+            resolve("size=3"),
+            "Unknown parameter `size`, expected one of: limit, start"
+        );
+        assert_eq!(
+            resolve("limit=-1"),
+            "Expected a whole number for `limit`, got `-1`"
+        );
+        assert_eq!(
+            resolve("start=100"),
+            "Value for `start` must be in 0..=99, got 100"
+        );
+        assert_eq!(
+            resolve("limit=20"),
+            "Value for `start` must be below `limit` (20), got 50"
+        );
+    }
+}
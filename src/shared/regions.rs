@@ -0,0 +1,253 @@
+use crate::shared::{Cells, DenseGrid, Direction, Neighbourhood, RowCol};
+use std::collections::{BTreeSet, HashSet};
+
+/// Pairs of orthogonal directions around each corner of a location, with the diagonal between
+static CORNERS: [(Direction, Direction, Direction); 4] = [
+    (Direction::Up, Direction::Right, Direction::UpRight),
+    (Direction::Right, Direction::Down, Direction::DownRight),
+    (Direction::Down, Direction::Left, Direction::DownLeft),
+    (Direction::Left, Direction::Up, Direction::UpLeft),
+];
+
+/// Symbols to render region labels with, they repeat after the last one
+const LABELS: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789";
+
+/// Connected group of locations that all hold the same cell
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Region<T> {
+    pub cell: T,
+    pub cells: BTreeSet<RowCol>, // Never empty
+    connectivity: Neighbourhood,
+}
+
+impl<T> Region<T> {
+    /// Number of locations
+    #[must_use]
+    pub fn area(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// Number of edges between a location of this region and one outside of it
+    #[must_use]
+    pub fn perimeter(&self) -> usize {
+        self.cells
+            .iter()
+            .map(|loc| {
+                loc.neighbours()
+                    .filter(|other| !self.cells.contains(other))
+                    .count()
+            })
+            .sum()
+    }
+
+    /// Number of straight sides of the outline, including those around holes
+    ///
+    /// This is the same as the number of corners.
+    #[must_use]
+    pub fn sides(&self) -> usize {
+        let inside = |loc: RowCol, dir: &Direction| self.cells.contains(&loc.step(dir));
+        self.cells
+            .iter()
+            .map(|&loc| {
+                CORNERS
+                    .iter()
+                    .filter(|(first, second, diagonal)| {
+                        match (inside(loc, first), inside(loc, second)) {
+                            (false, false) => true,                 // Outer corner
+                            (true, true) => !inside(loc, diagonal), // Inner corner
+                            _ => false,                             // Straight edge
+                        }
+                    })
+                    .count()
+            })
+            .sum()
+    }
+
+    /// Top-left and bottom-right corner of the smallest rectangle around the region
+    #[must_use]
+    pub fn bounding_box(&self) -> (RowCol, RowCol) {
+        let rows = self.cells.iter().map(|loc| loc.row);
+        let cols = self.cells.iter().map(|loc| loc.col);
+        (
+            RowCol::new(
+                rows.clone().min().unwrap_or(0),
+                cols.clone().min().unwrap_or(0),
+            ),
+            RowCol::new(rows.max().unwrap_or(0), cols.max().unwrap_or(0)),
+        )
+    }
+
+    /// Groups of locations that are not part of this region, but are enclosed by it
+    ///
+    /// The locations of a hole are connected orthogonally for a region with diagonal connections,
+    /// and the other way around, such that a hole cannot leak between two parts of the region.
+    #[must_use]
+    pub fn holes(&self) -> Vec<BTreeSet<RowCol>> {
+        let background = match self.connectivity {
+            Neighbourhood::VonNeumann => Neighbourhood::Moore,
+            _ => Neighbourhood::VonNeumann,
+        };
+        // With a margin around the region, everything outside of it is one connected group
+        let (top_left, bottom_right) = self.bounding_box();
+        let rows = (top_left.row - 1)..=(bottom_right.row + 1);
+        let cols = (top_left.col - 1)..=(bottom_right.col + 1);
+
+        let mut seen: HashSet<RowCol> = HashSet::new();
+        let mut holes = Vec::new();
+        for row in rows.clone() {
+            for col in cols.clone() {
+                let loc = RowCol::new(row, col);
+                if self.cells.contains(&loc) || seen.contains(&loc) {
+                    continue;
+                }
+                let mut group = BTreeSet::from([loc]);
+                let mut stack = vec![loc];
+                let mut enclosed = true;
+                while let Some(current) = stack.pop() {
+                    for other in current.neighbours_in(&background) {
+                        if !rows.contains(&other.row) || !cols.contains(&other.col) {
+                            enclosed = false; // Reached the margin, so this is the outside
+                        } else if !self.cells.contains(&other) && group.insert(other) {
+                            stack.push(other);
+                        }
+                    }
+                }
+                seen.extend(&group);
+                if enclosed {
+                    holes.push(group);
+                }
+            }
+        }
+        holes
+    }
+}
+
+/// Grid split into regions of connected, equal cells
+#[derive(Debug, Clone)]
+pub struct Regions<T> {
+    pub regions: Vec<Region<T>>, // Ordered by their first location, in reading order
+    pub labels: DenseGrid<Option<usize>>, // Index of the region per location, `None` when empty
+}
+
+impl<T: Clone + PartialEq> Regions<T> {
+    /// Label the connected regions of a grid, `connectivity` tells which locations connect
+    ///
+    /// Empty cells of a sparse grid are not part of any region.
+    pub fn label<G: Cells<Cell = T>>(grid: &G, connectivity: Neighbourhood) -> Self {
+        let mut labels = DenseGrid::new(grid.rows(), grid.cols(), None);
+        let mut regions = Vec::new();
+
+        for start in grid.range() {
+            let Some(cell) = grid.get(start) else {
+                continue;
+            };
+            if labels[start].is_some() {
+                continue;
+            }
+            let label = Some(regions.len());
+            labels[start] = label;
+            let mut cells = BTreeSet::from([start]);
+            let mut stack = vec![start];
+            while let Some(loc) = stack.pop() {
+                for other in grid.neighbours_within(loc, &connectivity) {
+                    if labels[other].is_none() && grid.get(other) == Some(cell) {
+                        labels[other] = label;
+                        cells.insert(other);
+                        stack.push(other);
+                    }
+                }
+            }
+            regions.push(Region {
+                cell: cell.clone(),
+                cells,
+                connectivity,
+            });
+        }
+
+        Self { regions, labels }
+    }
+
+    /// Region a location belongs to
+    #[must_use]
+    pub fn region_at(&self, loc: RowCol) -> Option<&Region<T>> {
+        let index = (*self.labels.get(loc)?)?;
+        self.regions.get(index)
+    }
+
+    /// Draw every location with a symbol for its region and `.` when empty
+    ///
+    /// Symbols are reused after 62 regions.
+    #[must_use]
+    pub fn render(&self) -> String {
+        self.labels
+            .map(|label| label.map_or('.', |index| char::from(LABELS[index % LABELS.len()])))
+            .to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::Grid;
+
+    #[test]
+    fn test_region_metrics() {
+        let garden = "AAAA\nBBCD\nBBCC\nEEEC\n";
+        let grid: DenseGrid<char> = DenseGrid::from(garden.lines());
+        let regions = Regions::label(&grid, Neighbourhood::VonNeumann);
+        let metrics: Vec<(char, usize, usize, usize)> = regions
+            .regions
+            .iter()
+            .map(|r| (r.cell, r.area(), r.perimeter(), r.sides()))
+            .collect();
+        assert_eq!(
+            metrics,
+            vec![
+                ('A', 4, 10, 4),
+                ('B', 4, 8, 4),
+                ('C', 4, 10, 8),
+                ('D', 1, 4, 4),
+                ('E', 3, 8, 4)
+            ]
+        );
+        let c = regions.region_at(RowCol::new(3, 3)).unwrap();
+        assert_eq!(c.bounding_box(), (RowCol::new(1, 2), RowCol::new(3, 3)));
+        assert_eq!(regions.render(), garden);
+    }
+
+    #[test]
+    fn test_region_holes() {
+        let grid: DenseGrid<char> = DenseGrid::from("OOOOO\nOXOXO\nOOOOO\nOXOXO\nOOOOO".lines());
+        let regions = Regions::label(&grid, Neighbourhood::VonNeumann);
+        let outer = &regions.regions[0];
+        assert_eq!(
+            (outer.area(), outer.perimeter(), outer.sides()),
+            (21, 36, 20)
+        );
+        assert_eq!(outer.holes().len(), 4);
+        assert!(outer.holes()[0].contains(&RowCol::new(1, 1)));
+        assert!(regions.regions[1].holes().is_empty());
+    }
+
+    #[test]
+    fn test_region_connectivity() {
+        let grid = Grid::from("X.\n.X\n".lines());
+        let diagonal = Regions::label(&grid, Neighbourhood::Moore);
+        assert_eq!(diagonal.regions.len(), 1);
+        assert_eq!(diagonal.render(), "A.\n.A\n");
+        assert_eq!(
+            Regions::label(&grid, Neighbourhood::VonNeumann)
+                .regions
+                .len(),
+            2
+        );
+
+        // A ring of diagonal connections still encloses its centre
+        let ring: DenseGrid<char> = DenseGrid::from(".#.\n#.#\n.#.".lines());
+        let regions = Regions::label(&ring, Neighbourhood::Moore);
+        let ring = regions.region_at(RowCol::new(0, 1)).unwrap();
+        assert_eq!(ring.holes(), vec![BTreeSet::from([RowCol::new(1, 1)])]);
+        let regions = Regions::label(&grid, Neighbourhood::VonNeumann);
+        assert!(regions.regions[0].holes().is_empty());
+    }
+}
@@ -0,0 +1,260 @@
+use crate::shared::{Cells, Neighbourhood, RowCol};
+use std::cmp::Reverse;
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+
+/// Lowest number of steps, or lowest cost, to get to each location that can be reached
+pub type Distances = HashMap<RowCol, u64>;
+
+/// Path finding over a grid, moving between neighbouring locations that are passable
+///
+/// Whether a location is passable is decided by its cell, `None` for an empty cell of a sparse
+/// grid. Locations outside the grid are never passable, the start always is.
+pub struct Search<'a, G, P> {
+    grid: &'a G,
+    passable: P,
+    neighbourhood: Neighbourhood,
+}
+
+/// Result of a cost-based search: the lowest costs and all equally cheap ways to get there
+struct Explored {
+    costs: Distances,
+    previous: HashMap<RowCol, Vec<RowCol>>, // Locations a cheapest path arrives from
+}
+
+impl<'a, G, P> Search<'a, G, P>
+where
+    G: Cells,
+    P: Fn(Option<&G::Cell>) -> bool,
+{
+    /// Search with orthogonal moves over the passable locations of a grid
+    pub fn new(grid: &'a G, passable: P) -> Self {
+        Self {
+            grid,
+            passable,
+            neighbourhood: Neighbourhood::VonNeumann,
+        }
+    }
+
+    /// Change the locations that can be moved to in a single step
+    #[must_use]
+    pub fn with_neighbourhood(mut self, neighbourhood: Neighbourhood) -> Self {
+        self.neighbourhood = neighbourhood;
+        self
+    }
+
+    /// Locations that can be moved to from a location
+    fn moves(&self, loc: RowCol) -> impl Iterator<Item = RowCol> {
+        self.grid
+            .neighbours_within(loc, &self.neighbourhood)
+            .filter(|&next| (self.passable)(self.grid.get(next)))
+    }
+
+    /// Lower bound of the cost to a goal, such that A* still finds the cheapest path
+    ///
+    /// Assumes every move costs at least 1.
+    fn estimate(&self, loc: RowCol, goal: RowCol) -> u64 {
+        let steps = match self.neighbourhood {
+            Neighbourhood::VonNeumann => loc.manhattan_distance(goal),
+            Neighbourhood::Moore => loc.chebyshev_distance(goal),
+            Neighbourhood::Radius(_) | Neighbourhood::Hex => 0, // Plain Dijkstra
+        };
+        steps.unsigned_abs().into()
+    }
+
+    /// Number of steps to every reachable location, by breadth-first search
+    #[must_use]
+    pub fn distances(&self, start: RowCol) -> Distances {
+        let mut distances = Distances::from([(start, 0)]);
+        let mut queue = VecDeque::from([start]);
+        while let Some(loc) = queue.pop_front() {
+            let steps = distances[&loc] + 1;
+            for next in self.moves(loc) {
+                if let Entry::Vacant(entry) = distances.entry(next) {
+                    entry.insert(steps);
+                    queue.push_back(next);
+                }
+            }
+        }
+        distances
+    }
+
+    /// All locations that can be reached from the start (flood fill), including the start itself
+    #[must_use]
+    pub fn reachable(&self, start: RowCol) -> HashSet<RowCol> {
+        self.distances(start).into_keys().collect()
+    }
+
+    /// Lowest cost to every reachable location, by Dijkstra's algorithm
+    ///
+    /// `cost` gives the cost of a single move, from and to a location.
+    #[must_use]
+    pub fn costs(&self, start: RowCol, cost: impl Fn(RowCol, RowCol) -> u64) -> Distances {
+        self.explore(start, None, &cost).costs
+    }
+
+    /// Cheapest path from the start to the goal (both included) with its cost, by A*
+    ///
+    /// Every move must cost at least 1, see [`Search::costs`] for `cost`.
+    #[must_use]
+    pub fn shortest_path(
+        &self,
+        start: RowCol,
+        goal: RowCol,
+        cost: impl Fn(RowCol, RowCol) -> u64,
+    ) -> Option<(u64, Vec<RowCol>)> {
+        let explored = self.explore(start, Some(goal), &cost);
+        let total = *explored.costs.get(&goal)?;
+
+        let mut path = vec![goal];
+        while let Some(previous) = explored.previous.get(path.last()?) {
+            path.push(previous[0]);
+        }
+        path.reverse();
+        Some((total, path))
+    }
+
+    /// Every cheapest path from the start to the goal with their cost, `None` if unreachable
+    ///
+    /// Every move must cost at least 1. The number of paths can grow quickly in open areas.
+    #[must_use]
+    pub fn all_shortest_paths(
+        &self,
+        start: RowCol,
+        goal: RowCol,
+        cost: impl Fn(RowCol, RowCol) -> u64,
+    ) -> Option<(u64, Vec<Vec<RowCol>>)> {
+        let explored = self.explore(start, Some(goal), &cost);
+        let total = *explored.costs.get(&goal)?;
+
+        let mut paths = Vec::new();
+        Self::collect_paths(&explored.previous, &mut vec![goal], &mut paths);
+        Some((total, paths))
+    }
+
+    /// Extend a path backwards to the start in every possible way
+    fn collect_paths(
+        previous: &HashMap<RowCol, Vec<RowCol>>,
+        path: &mut Vec<RowCol>, // From the end, in reverse
+        paths: &mut Vec<Vec<RowCol>>,
+    ) {
+        let Some(options) = path.last().and_then(|loc| previous.get(loc)) else {
+            paths.push(path.iter().rev().copied().collect()); // Reached the start
+            return;
+        };
+        for &option in options {
+            path.push(option);
+            Self::collect_paths(previous, path, paths);
+            path.pop();
+        }
+    }
+
+    /// Find the lowest costs from the start, stopping once the goal is reached (if given)
+    ///
+    /// Without a goal this is Dijkstra's algorithm, with a goal it is A* using [`Self::estimate`].
+    fn explore(
+        &self,
+        start: RowCol,
+        goal: Option<RowCol>,
+        cost: &impl Fn(RowCol, RowCol) -> u64,
+    ) -> Explored {
+        let estimate = |loc: RowCol| goal.map_or(0, |goal| self.estimate(loc, goal));
+        let mut costs = Distances::from([(start, 0)]);
+        let mut previous: HashMap<RowCol, Vec<RowCol>> = HashMap::new();
+        let mut queue = BinaryHeap::from([Reverse((estimate(start), 0, start))]);
+
+        while let Some(Reverse((expected, so_far, loc))) = queue.pop() {
+            if let Some(best) = goal.and_then(|goal| costs.get(&goal))
+                && expected > *best
+            {
+                break; // Anything else is more expensive than the goal
+            }
+            if so_far > costs[&loc] {
+                continue; // Found a cheaper way to this location after queueing it
+            }
+            for next in self.moves(loc) {
+                let total = so_far + cost(loc, next);
+                match costs.get(&next) {
+                    Some(&known) if total > known => {}
+                    Some(&known) if total == known => previous.entry(next).or_default().push(loc),
+                    _ => {
+                        costs.insert(next, total);
+                        previous.insert(next, vec![loc]);
+                        queue.push(Reverse((total + estimate(next), total, next)));
+                    }
+                }
+            }
+        }
+
+        Explored { costs, previous }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::{DenseGrid, Grid};
+
+    const MAZE: &str = "S..#\n.#..\n...#\n#..E\n";
+
+    /// Every move costs the same
+    fn step(_: RowCol, _: RowCol) -> u64 {
+        1
+    }
+
+    #[test]
+    fn test_search_distances() {
+        let grid: DenseGrid<char> = DenseGrid::from(MAZE.lines());
+        let search = Search::new(&grid, |cell| cell != Some(&'#'));
+        let (start, goal) = (RowCol::new(0, 0), RowCol::new(3, 3));
+
+        let distances = search.distances(start);
+        assert_eq!(distances[&goal], 6);
+        assert_eq!(distances.get(&RowCol::new(0, 3)), None);
+        assert_eq!(search.reachable(start).len(), 12);
+        assert_eq!(search.costs(start, step), distances);
+
+        // Leaving the right column is expensive
+        let costs = search.costs(start, |_, to| if to.col == 3 { 10 } else { 1 });
+        assert_eq!(costs[&goal], 15);
+
+        let diagonal = Search::new(&grid, |cell| cell != Some(&'#'))
+            .with_neighbourhood(Neighbourhood::Moore)
+            .distances(start);
+        assert_eq!(diagonal[&goal], 4);
+    }
+
+    #[test]
+    fn test_search_paths() {
+        let grid: DenseGrid<char> = DenseGrid::from(MAZE.lines());
+        let search = Search::new(&grid, |cell| cell != Some(&'#'));
+        let (start, goal) = (RowCol::new(0, 0), RowCol::new(3, 3));
+
+        let (total, path) = search.shortest_path(start, goal, step).unwrap();
+        assert_eq!(total, 6);
+        assert_eq!(path.len(), 7);
+        assert_eq!((path[0], path[6]), (start, goal));
+        assert!(path.windows(2).all(|w| w[0].manhattan_distance(w[1]) == 1));
+
+        let (total, paths) = search.all_shortest_paths(start, goal, step).unwrap();
+        assert_eq!(total, 6);
+        assert_eq!(paths.len(), 3);
+        assert!(paths.contains(&path));
+        assert!(
+            search
+                .shortest_path(start, RowCol::new(0, 3), step)
+                .is_none()
+        );
+        assert_eq!(
+            search.shortest_path(start, start, step),
+            Some((0, vec![start]))
+        );
+    }
+
+    #[test]
+    fn test_search_sparse() {
+        let grid = Grid::from(MAZE.lines());
+        let search = Search::new(&grid, |cell| cell != Some(&'#'));
+        assert_eq!(search.distances(RowCol::new(0, 0))[&RowCol::new(3, 3)], 6);
+    }
+}
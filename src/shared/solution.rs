@@ -1,58 +1,138 @@
-use std::fmt::{Display, Formatter};
-use std::fs::File;
-use std::io::BufReader;
+use crate::shared::{Outcome, SolveError, Trace};
+use std::any::Any;
+use std::io::Read;
 use std::ops::RangeInclusive;
-use std::path::PathBuf;
-
-/// Possible outcomes for a daily solver (either a number or a string directly)
-#[derive(PartialEq, Debug)]
-#[allow(dead_code)]
-pub enum Outcome {
-    U64(u64),
-    Text(String),
-}
 
-/// Implement string conversion for our general `Outcome` enum
-impl Display for Outcome {
-    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
-        match self {
-            Outcome::U64(n) => write!(f, "{n}"),
-            Outcome::Text(txt) => write!(f, "{txt}"),
-        }
-    }
-}
+/// Result of a daily solver
+pub type SolveResult = Result<Outcome, SolveError>;
 
 /// Base behavior of the daily solutions
+///
+/// The input text is parsed once into [`Solution::Parsed`], which both parts then work on.
+/// See [`read_input`] to get the text from a file or stdin.
 pub trait Solution {
-    /// Helper initialize method - default implementation is empty
-    fn init(&self) {}
+    /// Input as the parts work on it, e.g. a list of numbers or a grid
+    type Parsed: 'static;
 
-    /// Main run method, it just picks the right solution method
-    fn run(&self, input_file: PathBuf, part: u8) -> Outcome {
-        self.init(); // Helper for future implementations for shared logic across parts
-
-        match part {
-            1 => self.run_part_1(input_file),
-            2 => self.run_part_2(input_file),
-            _ => panic!("Cannot do anything with part {part}"), // Also validated by CLI
-        }
-    }
+    /// Turn the complete input text into the parsed form
+    ///
+    /// Intermediate state can be emitted through `trace`, also in the parts.
+    ///
+    /// # Errors
+    ///
+    /// If the input is invalid.
+    fn parse(&self, input: &str, trace: &Trace) -> Result<Self::Parsed, SolveError>;
 
     /// Solution for part 1 (must be implemented)
-    fn run_part_1(&self, input_file: PathBuf) -> Outcome;
+    ///
+    /// # Errors
+    ///
+    /// If the input is invalid or has no solution.
+    fn part_1(&self, parsed: &Self::Parsed, trace: &Trace) -> SolveResult;
 
     /// Solution for part 2 (must be implemented)
-    fn run_part_2(&self, input_file: PathBuf) -> Outcome;
+    ///
+    /// # Errors
+    ///
+    /// If the input is invalid or has no solution.
+    fn part_2(&self, parsed: &Self::Parsed, trace: &Trace) -> SolveResult;
+
+    /// Parse the input and solve part 1
+    ///
+    /// # Errors
+    ///
+    /// If the input is invalid or has no solution.
+    fn run_part_1(&self, input: &str, trace: &Trace) -> SolveResult {
+        self.part_1(&self.parse(input, trace)?, trace)
+    }
 
-    /// Create a reader object for the input file
-    fn get_file_reader(&self, input_file: PathBuf) -> BufReader<File> {
-        let file = File::open(input_file).unwrap();
-        BufReader::new(file)
+    /// Parse the input and solve part 2
+    ///
+    /// # Errors
+    ///
+    /// If the input is invalid or has no solution.
+    fn run_part_2(&self, input: &str, trace: &Trace) -> SolveResult {
+        self.part_2(&self.parse(input, trace)?, trace)
     }
 
     /// Make a Range object from a string like "11-22" (inclusive start and end)
-    fn get_range_from_line(&self, line: &str) -> RangeInclusive<u64> {
-        let mut split = line.split('-');
-        split.next().unwrap().parse().unwrap()..=split.next().unwrap().parse().unwrap()
+    ///
+    /// # Errors
+    ///
+    /// If the line is not formatted like a range.
+    fn get_range_from_line(&self, line: &str) -> Result<RangeInclusive<u64>, SolveError> {
+        let parse_error = || SolveError::parse(format!("Expected `a-b` range, got `{line}`"));
+        let (start, end) = line.split_once('-').ok_or_else(parse_error)?;
+        let start = start.parse().map_err(|_| parse_error())?;
+        let end = end.parse().map_err(|_| parse_error())?;
+        Ok(start..=end)
     }
 }
+
+/// Parsed input of any solver, see [`Solver::parse_input`]
+pub type ParsedInput = Box<dyn Any>;
+
+/// Form of a [`Solution`] that hides its parsed type, so solvers can be picked at run time
+///
+/// Implemented for every solution.
+pub trait Solver {
+    /// Parse the input text, to pass to [`Solver::solve_part`] for any part
+    ///
+    /// # Errors
+    ///
+    /// If the input is invalid.
+    fn parse_input(&self, input: &str, trace: &Trace) -> Result<ParsedInput, SolveError>;
+
+    /// Solve a part from input parsed by this same solver
+    ///
+    /// # Errors
+    ///
+    /// If the input is invalid or has no solution.
+    ///
+    /// # Panics
+    ///
+    /// If `part` is not 1 or 2, or `parsed` came from another solver.
+    fn solve_part(&self, parsed: &ParsedInput, part: u8, trace: &Trace) -> SolveResult;
+
+    /// Main run method, parses the input and picks the right solution method
+    ///
+    /// # Errors
+    ///
+    /// If the input is invalid or has no solution.
+    ///
+    /// # Panics
+    ///
+    /// If `part` is not 1 or 2.
+    fn run(&self, input: &str, part: u8, trace: &Trace) -> SolveResult {
+        let parsed = self.parse_input(input, trace)?;
+        self.solve_part(&parsed, part, trace)
+    }
+}
+
+impl<S: Solution> Solver for S {
+    fn parse_input(&self, input: &str, trace: &Trace) -> Result<ParsedInput, SolveError> {
+        Ok(Box::new(self.parse(input, trace)?))
+    }
+
+    fn solve_part(&self, parsed: &ParsedInput, part: u8, trace: &Trace) -> SolveResult {
+        let parsed = parsed
+            .downcast_ref::<S::Parsed>()
+            .expect("Input was parsed by another solver");
+        match part {
+            1 => self.part_1(parsed, trace),
+            2 => self.part_2(parsed, trace),
+            _ => panic!("Cannot do anything with part {part}"), // Also validated by CLI
+        }
+    }
+}
+
+/// Read the complete input text from any reader, like a file or stdin
+///
+/// # Errors
+///
+/// If reading fails or the input is not valid UTF-8.
+pub fn read_input(mut reader: impl Read) -> Result<String, SolveError> {
+    let mut input = String::new();
+    reader.read_to_string(&mut input)?;
+    Ok(input)
+}
@@ -0,0 +1,149 @@
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::sync::Mutex;
+
+/// Verbosity of a trace message, higher levels give more detail
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Info = 1,  // Big steps, like merging circuits
+    Debug = 2, // Intermediate state per input line or row
+    Trace = 3, // Anything else
+}
+
+/// Destination of the trace messages
+#[derive(Debug)]
+enum Sink {
+    Stderr,
+    File(BufWriter<File>),
+    Memory(Vec<String>),
+}
+
+/// Context for solvers to emit intermediate state while solving
+///
+/// Messages never end up on stdout, so they won't pollute the answer.
+#[derive(Debug)]
+pub struct Trace {
+    verbosity: u8, // 0 means disabled
+    sink: Mutex<Sink>,
+}
+
+impl Trace {
+    fn new(verbosity: u8, sink: Sink) -> Self {
+        Self {
+            verbosity,
+            sink: Mutex::new(sink),
+        }
+    }
+
+    /// Trace that ignores all messages
+    #[must_use]
+    pub fn disabled() -> Self {
+        Self::new(0, Sink::Memory(Vec::new()))
+    }
+
+    /// Trace writing messages up to `verbosity` to stderr
+    #[must_use]
+    pub fn to_stderr(verbosity: u8) -> Self {
+        Self::new(verbosity, Sink::Stderr)
+    }
+
+    /// Trace writing messages up to `verbosity` to a (new) file
+    ///
+    /// # Errors
+    ///
+    /// If the file cannot be created.
+    pub fn to_file(verbosity: u8, path: &Path) -> io::Result<Self> {
+        let file = File::create(path)?;
+        Ok(Self::new(verbosity, Sink::File(BufWriter::new(file))))
+    }
+
+    /// Trace keeping messages up to `verbosity` in memory, see [`Trace::take_messages`]
+    #[must_use]
+    pub fn to_memory(verbosity: u8) -> Self {
+        Self::new(verbosity, Sink::Memory(Vec::new()))
+    }
+
+    /// Return true if messages of this level will be kept
+    ///
+    /// Useful to skip preparing expensive messages.
+    #[must_use]
+    pub fn enabled(&self, level: Level) -> bool {
+        level as u8 <= self.verbosity
+    }
+
+    /// Emit a message, which is only formatted when the level is enabled
+    pub fn log(&self, level: Level, message: impl FnOnce() -> String) {
+        if !self.enabled(level) {
+            return;
+        }
+        let message = message();
+        let mut sink = self
+            .sink
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        match &mut *sink {
+            Sink::Stderr => eprintln!("{message}"),
+            // Failing to write a trace should not stop the solver
+            Sink::File(writer) => {
+                let _ = writeln!(writer, "{message}");
+            }
+            Sink::Memory(messages) => messages.push(message),
+        }
+    }
+
+    /// Make sure all messages are written out
+    pub fn flush(&self) {
+        let mut sink = self
+            .sink
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        if let Sink::File(writer) = &mut *sink {
+            let _ = writer.flush();
+        }
+    }
+
+    /// Get (and clear) the messages kept in memory
+    #[must_use]
+    pub fn take_messages(&self) -> Vec<String> {
+        let mut sink = self
+            .sink
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        match &mut *sink {
+            Sink::Memory(messages) => std::mem::take(messages),
+            _ => Vec::new(),
+        }
+    }
+}
+
+impl Drop for Trace {
+    fn drop(&mut self) {
+        self.flush();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trace_levels() {
+        let trace = Trace::to_memory(2);
+        assert!(trace.enabled(Level::Debug));
+        assert!(!trace.enabled(Level::Trace));
+
+        trace.log(Level::Info, || String::from("one"));
+        trace.log(Level::Debug, || String::from("two"));
+        trace.log(Level::Trace, || panic!("Should not be formatted"));
+        assert_eq!(trace.take_messages(), vec!["one", "two"]);
+        assert!(trace.take_messages().is_empty());
+    }
+
+    #[test]
+    fn trace_disabled() {
+        let trace = Trace::disabled();
+        trace.log(Level::Info, || String::from("one"));
+        assert!(trace.take_messages().is_empty());
+    }
+}
@@ -0,0 +1,263 @@
+use crate::shared::coordinates::I;
+use crate::shared::{Cells, DenseGrid, RowCol};
+
+/// The 8 ways to turn or mirror a grid onto itself
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Symmetry {
+    Identity,
+    /// Quarter turn clockwise
+    Rotate90,
+    Rotate180,
+    /// Quarter turn counter-clockwise
+    Rotate270,
+    /// Mirror left to right
+    FlipHorizontal,
+    /// Mirror top to bottom
+    FlipVertical,
+    /// Mirror along the diagonal from the top-left, swapping rows and columns
+    Transpose,
+    /// Mirror along the diagonal from the top-right
+    AntiTranspose,
+}
+
+impl Symmetry {
+    pub const ALL: [Symmetry; 8] = [
+        Symmetry::Identity,
+        Symmetry::Rotate90,
+        Symmetry::Rotate180,
+        Symmetry::Rotate270,
+        Symmetry::FlipHorizontal,
+        Symmetry::FlipVertical,
+        Symmetry::Transpose,
+        Symmetry::AntiTranspose,
+    ];
+
+    /// Whether the rows become columns
+    fn swaps_axes(self) -> bool {
+        matches!(
+            self,
+            Symmetry::Rotate90
+                | Symmetry::Rotate270
+                | Symmetry::Transpose
+                | Symmetry::AntiTranspose
+        )
+    }
+
+    /// Location in the original grid (of `rows` by `cols`) that ends up at `loc`
+    fn source(self, loc: RowCol, rows: I, cols: I) -> RowCol {
+        let RowCol { row, col } = loc;
+        match self {
+            Symmetry::Identity => loc,
+            Symmetry::Rotate90 => RowCol::new(rows - 1 - col, row),
+            Symmetry::Rotate180 => RowCol::new(rows - 1 - row, cols - 1 - col),
+            Symmetry::Rotate270 => RowCol::new(col, cols - 1 - row),
+            Symmetry::FlipHorizontal => RowCol::new(row, cols - 1 - col),
+            Symmetry::FlipVertical => RowCol::new(rows - 1 - row, col),
+            Symmetry::Transpose => RowCol::new(col, row),
+            Symmetry::AntiTranspose => RowCol::new(rows - 1 - col, cols - 1 - row),
+        }
+    }
+}
+
+impl<T: Clone> DenseGrid<T> {
+    /// Turn or mirror the grid
+    #[must_use]
+    pub fn transform(&self, symmetry: Symmetry) -> Self {
+        let (rows, cols) = (self.rows(), self.cols());
+        let (new_rows, new_cols) = if symmetry.swaps_axes() {
+            (cols, rows)
+        } else {
+            (rows, cols)
+        };
+        Self::from_fn(new_rows, new_cols, |loc| {
+            self[symmetry.source(loc, rows, cols)].clone()
+        })
+    }
+
+    /// Turn the grid a quarter clockwise
+    #[must_use]
+    pub fn rotate_90(&self) -> Self {
+        self.transform(Symmetry::Rotate90)
+    }
+
+    /// Turn the grid upside down
+    #[must_use]
+    pub fn rotate_180(&self) -> Self {
+        self.transform(Symmetry::Rotate180)
+    }
+
+    /// Turn the grid a quarter counter-clockwise
+    #[must_use]
+    pub fn rotate_270(&self) -> Self {
+        self.transform(Symmetry::Rotate270)
+    }
+
+    /// Mirror the grid left to right
+    #[must_use]
+    pub fn flip_horizontal(&self) -> Self {
+        self.transform(Symmetry::FlipHorizontal)
+    }
+
+    /// Mirror the grid top to bottom
+    #[must_use]
+    pub fn flip_vertical(&self) -> Self {
+        self.transform(Symmetry::FlipVertical)
+    }
+
+    /// Swap rows and columns, such that columns can be read as rows
+    #[must_use]
+    pub fn transpose(&self) -> Self {
+        self.transform(Symmetry::Transpose)
+    }
+
+    /// The grid in every orientation, in the order of [`Symmetry::ALL`]
+    ///
+    /// A symmetric grid gives the same result more than once.
+    #[must_use]
+    pub fn symmetries(&self) -> Vec<Self> {
+        Symmetry::ALL
+            .iter()
+            .map(|&symmetry| self.transform(symmetry))
+            .collect()
+    }
+
+    /// Orientation that is the same for all turned and mirrored versions of this grid
+    ///
+    /// This is the smallest of the [`DenseGrid::symmetries`], such that shapes can be compared or used
+    /// as keys regardless of their orientation.
+    ///
+    /// # Panics
+    ///
+    /// Never, there is always at least one symmetry.
+    #[must_use]
+    pub fn canonical(&self) -> Self
+    where
+        T: Ord,
+    {
+        self.symmetries()
+            .into_iter()
+            .min()
+            .expect("There are always 8 symmetries")
+    }
+
+    /// Part of the grid between two corners (both included), `None` if it is not within the grid
+    #[must_use]
+    pub fn crop(&self, top_left: RowCol, bottom_right: RowCol) -> Option<Self> {
+        if !self.contains(top_left) || !self.contains(bottom_right) {
+            return None;
+        }
+        let size = RowCol::new(
+            bottom_right.row - top_left.row + 1,
+            bottom_right.col - top_left.col + 1,
+        );
+        if size.row <= 0 || size.col <= 0 {
+            return None;
+        }
+        Some(Self::from_fn(size.row, size.col, |loc| {
+            self[top_left + loc].clone()
+        }))
+    }
+
+    /// Surround the grid by `margin` extra rows and columns of `value` on every side
+    ///
+    /// # Panics
+    ///
+    /// If the margin is negative.
+    #[must_use]
+    pub fn pad(&self, margin: I, value: &T) -> Self {
+        assert!(margin >= 0, "Margin {margin} is negative");
+        let offset = RowCol::new(-margin, -margin);
+        Self::from_fn(self.rows() + 2 * margin, self.cols() + 2 * margin, |loc| {
+            self.get(loc + offset).unwrap_or(value).clone()
+        })
+    }
+
+    /// Repeat the grid a number of times downwards and across
+    ///
+    /// # Panics
+    ///
+    /// If a count is negative.
+    #[must_use]
+    pub fn tile(&self, down: I, across: I) -> Self {
+        assert!(
+            down >= 0 && across >= 0,
+            "Tile counts {down}x{across} are negative"
+        );
+        Self::from_fn(self.rows() * down, self.cols() * across, |loc| {
+            self.get_repeated(loc).clone()
+        })
+    }
+}
+
+impl<T> DenseGrid<T> {
+    /// Cell at any location, as if the grid repeats itself endlessly in every direction
+    ///
+    /// # Panics
+    ///
+    /// If the grid is empty.
+    #[must_use]
+    pub fn get_repeated(&self, loc: RowCol) -> &T {
+        assert!(self.rows() > 0 && self.cols() > 0, "Grid is empty");
+        let wrapped = RowCol::new(
+            loc.row.rem_euclid(self.rows()),
+            loc.col.rem_euclid(self.cols()),
+        );
+        &self[wrapped]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Grid from lines, with the rows separated by `/`
+    fn grid(text: &str) -> DenseGrid<char> {
+        DenseGrid::from(text.replace('/', "\n").lines())
+    }
+
+    #[test]
+    fn test_grid_symmetries() {
+        let shape = grid("abc/def");
+        assert_eq!(shape.rotate_90(), grid("da/eb/fc"));
+        assert_eq!(shape.rotate_180(), grid("fed/cba"));
+        assert_eq!(shape.rotate_270(), grid("cf/be/ad"));
+        assert_eq!(shape.flip_horizontal(), grid("cba/fed"));
+        assert_eq!(shape.flip_vertical(), grid("def/abc"));
+        assert_eq!(shape.transpose(), grid("ad/be/cf"));
+        assert_eq!(shape.transform(Symmetry::AntiTranspose), grid("fc/eb/da"));
+        assert_eq!(shape.rotate_90().rotate_270(), shape);
+
+        // Every orientation of the same shape has the same canonical form
+        let canonical = shape.canonical();
+        assert!(
+            shape
+                .symmetries()
+                .iter()
+                .all(|s| s.canonical() == canonical)
+        );
+        assert_ne!(grid("abc/fed").canonical(), canonical);
+        let symmetric = grid(".#./###/.#.");
+        assert_eq!(symmetric.canonical(), symmetric);
+    }
+
+    #[test]
+    fn test_grid_crop_pad_tile() {
+        let shape = grid("abc/def");
+        assert_eq!(
+            shape.crop(RowCol::new(0, 1), RowCol::new(1, 2)),
+            Some(grid("bc/ef"))
+        );
+        assert_eq!(shape.crop(RowCol::new(0, 1), RowCol::new(2, 2)), None);
+        assert_eq!(shape.crop(RowCol::new(1, 1), RowCol::new(0, 1)), None);
+
+        assert_eq!(shape.pad(1, &'.'), grid("...../.abc./.def./....."));
+        assert_eq!(shape.tile(2, 2), grid("abcabc/defdef/abcabc/defdef"));
+        assert_eq!(shape.get_repeated(RowCol::new(-1, 7)), &'e');
+    }
+
+    #[test]
+    #[should_panic(expected = "negative")]
+    fn test_grid_tile_negative() {
+        let _ = DenseGrid::new(0, 2, '.').tile(-1, 1);
+    }
+}
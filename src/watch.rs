@@ -0,0 +1,212 @@
+//! Re-run a day whenever its solver, samples or input change
+//!
+//! Changes are found by polling modification times. Every run goes through `cargo run`, such that
+//! the solver is rebuilt first, and its answers are compared with those of the previous run.
+
+use crate::shared::Outcome;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::SystemTime;
+
+/// Files and directories that affect the answers of a day, for the input it is solved for
+#[must_use]
+pub fn watched_paths(day: u8, input: &Path) -> Vec<PathBuf> {
+    vec![
+        PathBuf::from(format!("src/days/day_{day:02}.rs")),
+        PathBuf::from(format!("tests/day_{day:02}")),
+        input.to_path_buf(),
+    ]
+}
+
+/// Modification time of each watched file, directories are expanded one level
+#[derive(Debug, Default, PartialEq)]
+pub struct Snapshot {
+    modified: BTreeMap<PathBuf, Option<SystemTime>>, // `None` for a missing file
+}
+
+impl Snapshot {
+    /// Look at the files now
+    #[must_use]
+    pub fn take(paths: &[PathBuf]) -> Self {
+        let mut modified = BTreeMap::new();
+        for path in paths {
+            let files = match fs::read_dir(path) {
+                Ok(entries) => entries.filter_map(|e| e.ok().map(|e| e.path())).collect(),
+                Err(_) => vec![path.clone()],
+            };
+            for file in files {
+                let time = fs::metadata(&file).and_then(|m| m.modified()).ok();
+                modified.insert(file, time);
+            }
+        }
+        Self { modified }
+    }
+
+    /// Files that were added, removed or modified since an earlier snapshot
+    #[must_use]
+    pub fn changes(&self, earlier: &Snapshot) -> Vec<PathBuf> {
+        let mut changed: Vec<PathBuf> = self
+            .modified
+            .iter()
+            .filter(|(path, time)| earlier.modified.get(*path) != Some(time))
+            .map(|(path, _)| path.clone())
+            .collect();
+        changed.extend(
+            earlier
+                .modified
+                .keys()
+                .filter(|path| !self.modified.contains_key(*path))
+                .cloned(),
+        );
+        changed
+    }
+}
+
+/// Answers of a single run, per part
+pub type Answers = BTreeMap<u8, Result<Outcome, String>>;
+
+/// Answer as printed by `--format json`
+#[derive(Debug, Deserialize)]
+struct Record {
+    part: u8,
+    outcome: Option<Outcome>,
+    error: Option<String>,
+}
+
+/// Build `cargo run` for this executable, in the same profile and with the same features
+fn cargo_run(args: &[String]) -> Command {
+    let mut command = Command::new(env::var("CARGO").unwrap_or_else(|_| String::from("cargo")));
+    command.args(["run", "--quiet"]);
+    if !cfg!(debug_assertions) {
+        command.arg("--release");
+    }
+    if cfg!(feature = "parallel") {
+        command.args(["--features", "parallel"]);
+    }
+    command.arg("--").args(args);
+    command
+}
+
+/// Check the samples of a day in a fresh build, `false` when any fails (or the build does)
+///
+/// # Errors
+///
+/// If cargo cannot be started.
+pub fn run_samples(day: u8) -> Result<bool, String> {
+    let status = cargo_run(&[String::from("samples"), day.to_string()])
+        .status()
+        .map_err(|err| format!("Failed to run cargo: {err}"))?;
+    Ok(status.success())
+}
+
+/// Solve parts of a day for an input in a fresh build
+///
+/// # Errors
+///
+/// If cargo cannot be started, or the build fails.
+pub fn run_answers(day: u8, parts: &[u8], input: &Path) -> Result<Answers, String> {
+    let part = match parts {
+        [part] => part.to_string(),
+        _ => String::from("both"),
+    };
+    let args = [
+        day.to_string(),
+        String::from("-p"),
+        part,
+        String::from("-f"),
+        String::from("json"),
+        String::from("-i"),
+        input.display().to_string(),
+    ];
+    let output = cargo_run(&args)
+        .stderr(Stdio::inherit())
+        .output()
+        .map_err(|err| format!("Failed to run cargo: {err}"))?;
+    let records: Vec<Record> = serde_json::from_slice(&output.stdout)
+        .map_err(|_| String::from("No answers, the build failed"))?;
+    Ok(records
+        .into_iter()
+        .map(|record| {
+            let answer = record
+                .outcome
+                .ok_or_else(|| record.error.unwrap_or_default());
+            (record.part, answer)
+        })
+        .collect())
+}
+
+/// Describe the answers in one line, compared with the answers of the previous run
+#[must_use]
+pub fn summary(answers: &Answers, previous: Option<&Answers>) -> String {
+    let parts: Vec<String> = answers
+        .iter()
+        .map(|(part, answer)| {
+            let before = previous.and_then(|previous| previous.get(part));
+            let text = match answer {
+                Ok(outcome) => outcome.to_string().replace('\n', " / "),
+                Err(err) => format!("ERROR {err}"),
+            };
+            let change = match (before, answer) {
+                (None, _) | (Some(Err(_)), Err(_)) => String::new(),
+                (Some(Ok(old)), Ok(new)) if old == new => String::from(" (same)"),
+                (Some(Ok(old)), _) => format!(" (was {})", old.to_string().replace('\n', " / ")),
+                (Some(Err(_)), Ok(_)) => String::from(" (was an error)"),
+            };
+            format!("part {part}: {text}{change}")
+        })
+        .collect();
+    parts.join(" | ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snapshot_changes() {
+        let dir = env::temp_dir().join(format!("aoc_watch_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("sample.txt");
+        let paths = [dir.clone(), dir.join("missing.txt")];
+
+        let before = Snapshot::take(&paths);
+        fs::write(&file, "1\n").unwrap();
+        let after = Snapshot::take(&paths);
+        assert_eq!(after.changes(&before), vec![file.clone()]);
+        assert!(Snapshot::take(&paths).changes(&after).is_empty());
+
+        fs::remove_file(&file).unwrap();
+        assert_eq!(Snapshot::take(&paths).changes(&after), vec![file.clone()]);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn watch_input() {
+        let input = Path::new("puzzles/day_03.txt");
+        let paths = watched_paths(3, input);
+        assert!(paths.contains(&input.to_path_buf()));
+        assert!(paths.contains(&PathBuf::from("src/days/day_03.rs")));
+    }
+
+    #[test]
+    fn summary_with_diff() {
+        let previous = Answers::from([(1, Ok(Outcome::U64(21))), (2, Ok(Outcome::U64(40)))]);
+        let answers = Answers::from([
+            (1, Ok(Outcome::U64(21))),
+            (2, Err(String::from("Overflow: total does not fit"))),
+        ]);
+        assert_eq!(summary(&previous, None), "part 1: 21 | part 2: 40");
+        assert_eq!(
+            summary(&answers, Some(&previous)),
+            "part 1: 21 (same) | part 2: ERROR Overflow: total does not fit (was 40)"
+        );
+        assert_eq!(
+            summary(&previous, Some(&answers)),
+            "part 1: 21 (same) | part 2: 40 (was an error)"
+        );
+    }
+}
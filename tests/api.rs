@@ -0,0 +1,60 @@
+use advent_of_code_2025::days::{Day05, get_solver};
+use advent_of_code_2025::shared::{Grid, Outcome, RowCol, Trace};
+use std::fs;
+
+#[test]
+fn solver_from_library() {
+    let input = fs::read_to_string("tests/day_07/sample.txt").unwrap();
+    let solver = get_solver(7);
+    assert_eq!(
+        solver.run(&input, 1, &Trace::disabled()).unwrap(),
+        Outcome::U64(21)
+    );
+    assert_eq!(
+        solver.run(&input, 2, &Trace::disabled()).unwrap(),
+        Outcome::U64(40)
+    );
+}
+
+#[test]
+fn parse_once_from_library() {
+    let input = fs::read_to_string("tests/day_09/sample.txt").unwrap();
+    let solver = get_solver(9);
+    let trace = Trace::disabled();
+    let parsed = solver.parse_input(&input, &trace).unwrap();
+    assert_eq!(
+        solver.solve_part(&parsed, 1, &trace).unwrap(),
+        Outcome::U64(50)
+    );
+    assert_eq!(
+        solver.solve_part(&parsed, 2, &trace).unwrap(),
+        Outcome::U64(24)
+    );
+}
+
+#[test]
+fn solver_error_from_library() {
+    let solver = get_solver(1);
+    let err = solver.run("R10\nQ5\n", 1, &Trace::disabled()).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "line 2, column 1: Expected `L` or `R`, got `Q`"
+    );
+}
+
+#[test]
+fn grid_from_library() {
+    let grid = Grid::from("..@\n@.@\n".lines());
+    assert_eq!((grid.rows, grid.cols), (2, 3));
+    let neighbours: Vec<(RowCol, char)> = grid.neighbouring_items(&RowCol::new(1, 2)).collect();
+    assert_eq!(neighbours, vec![(RowCol::new(0, 2), '@')]);
+}
+
+#[test]
+fn interval_merging_from_library() {
+    let mut ranges = Vec::new();
+    for range in [3..=5, 10..=14, 16..=20, 12..=18] {
+        ranges = Day05::combine_inclusive_range(ranges, range);
+    }
+    assert_eq!(Day05::count_ranges_list(ranges), 3 + 11);
+}
@@ -0,0 +1,93 @@
+//! Local stand-in for the puzzle website, such that tests need no real network
+
+#![allow(dead_code)] // Each test crate uses a different part of it
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::sync::mpsc;
+use std::thread;
+
+/// Request as received by the stand-in
+#[derive(Debug)]
+pub struct Request {
+    pub line: String, // Like `GET /2025/day/1/input HTTP/1.1`
+    pub headers: Vec<String>,
+    pub body: String,
+}
+
+impl Request {
+    /// Whether the request carries the session token `secret`
+    pub fn authorized(&self) -> bool {
+        self.headers
+            .iter()
+            .any(|header| header.eq_ignore_ascii_case("cookie: session=secret"))
+    }
+
+    /// Day in a path like `/2025/day/<day>/...`
+    pub fn day(&self) -> Option<u8> {
+        self.line
+            .split_whitespace()
+            .nth(1)?
+            .strip_prefix("/2025/day/")?
+            .split('/')
+            .next()?
+            .parse()
+            .ok()
+    }
+}
+
+/// Serve `count` connections, answering with the status and body from `respond`
+///
+/// Returns the base URL, and a channel that gets every handled request.
+pub fn stand_in(
+    count: usize,
+    respond: fn(&Request) -> (&'static str, String),
+) -> (String, mpsc::Receiver<Request>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+    let (log, requests) = mpsc::channel();
+    thread::spawn(move || {
+        for stream in listener.incoming().take(count) {
+            let mut stream = stream.unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            let mut headers = Vec::new();
+            loop {
+                let mut header = String::new();
+                reader.read_line(&mut header).unwrap();
+                if header.trim().is_empty() {
+                    break;
+                }
+                headers.push(header.trim().to_string());
+            }
+            let length = headers
+                .iter()
+                .find_map(|h| {
+                    h.to_ascii_lowercase()
+                        .strip_prefix("content-length:")?
+                        .trim()
+                        .parse()
+                        .ok()
+                })
+                .unwrap_or(0);
+            let mut body = vec![0; length];
+            reader.read_exact(&mut body).unwrap();
+
+            let request = Request {
+                line: line.trim().to_string(),
+                headers,
+                body: String::from_utf8(body).unwrap(),
+            };
+            let (status, body) = respond(&request);
+            write!(
+                stream,
+                "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            )
+            .unwrap();
+            log.send(request).unwrap();
+        }
+    });
+    (base_url, requests)
+}
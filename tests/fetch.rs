@@ -0,0 +1,48 @@
+mod common;
+
+use advent_of_code_2025::fetch::{Fetcher, HttpFetcher, InputCache};
+use common::{Request, stand_in};
+use std::time::{Duration, Instant};
+use std::{env, fs};
+
+/// Serve inputs for days up to 12, only with the right session
+fn inputs(request: &Request) -> (&'static str, String) {
+    match request.day() {
+        _ if !request.authorized() => ("400 Bad Request", String::from("Log in first\n")),
+        Some(day) if day <= 12 => ("200 OK", format!("input of day {day}\n")),
+        _ => ("404 Not Found", String::from("Not found\n")),
+    }
+}
+
+#[test]
+fn fetch_from_stand_in() {
+    let (base_url, requests) = stand_in(3, inputs);
+    let interval = Duration::from_millis(200);
+    let fetcher = HttpFetcher::new(&base_url, "secret").with_interval(interval);
+
+    let start = Instant::now();
+    assert_eq!(fetcher.fetch(1).unwrap(), "input of day 1\n");
+    assert!(fetcher.fetch(13).unwrap_err().contains("404"));
+    assert!(start.elapsed() >= interval, "Requests are rate-limited");
+
+    let wrong_session = HttpFetcher::new(&base_url, "guess");
+    assert!(wrong_session.fetch(2).is_err());
+
+    let received: Vec<Request> = requests.iter().take(3).collect();
+    assert_eq!(received[0].line, "GET /2025/day/1/input HTTP/1.1");
+}
+
+#[test]
+fn cache_from_stand_in() {
+    let (base_url, requests) = stand_in(1, inputs);
+    let fetcher = HttpFetcher::new(&base_url, "secret");
+    let dir = env::temp_dir().join(format!("aoc_fetch_{}", std::process::id()));
+    let cache = InputCache::new(&dir, Some(Box::new(fetcher)));
+
+    let path = cache.ensure(5).unwrap();
+    assert_eq!(fs::read_to_string(&path).unwrap(), "input of day 5\n");
+    assert_eq!(cache.ensure(5).unwrap(), path); // Server only answers once
+    assert_eq!(requests.iter().count(), 1);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,10 @@
+use advent_of_code_2025::samples::{self, SAMPLES_DIR};
+use std::path::Path;
+
+#[test]
+fn all_samples() {
+    let samples = samples::discover(Path::new(SAMPLES_DIR)).unwrap();
+    assert!(!samples.is_empty(), "No samples found in {SAMPLES_DIR}");
+    let failures: Vec<String> = samples.iter().flat_map(samples::Sample::check).collect();
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
@@ -0,0 +1,54 @@
+mod common;
+
+use advent_of_code_2025::journal::{self, HttpSubmitter, Journal, Verdict};
+use advent_of_code_2025::shared::Outcome;
+use common::{Request, stand_in};
+
+/// Judge answers like the website, the right answer is 42
+fn judge(request: &Request) -> (&'static str, String) {
+    let answer = request
+        .body
+        .split('&')
+        .find_map(|field| field.strip_prefix("answer="))
+        .and_then(|answer| answer.parse::<i64>().ok());
+    let message = match answer {
+        _ if !request.authorized() => return ("400 Bad Request", String::from("Log in first")),
+        Some(42) => "That's the right answer! You are one gold star closer.",
+        Some(n) if n > 42 => "That's not the right answer; your answer is too high.",
+        Some(_) => "That's not the right answer; your answer is too low.",
+        None => "That's not the right answer.",
+    };
+    (
+        "200 OK",
+        format!("<main><article><p>{message}</p></article></main>"),
+    )
+}
+
+#[test]
+fn submit_to_stand_in() {
+    let (base_url, requests) = stand_in(3, judge);
+    let submitter = HttpSubmitter::new(&base_url, "secret");
+    let mut journal = Journal::default();
+    let mut submit =
+        |answer: Outcome| journal::submit(&mut journal, &submitter, (3, 2), &answer, false);
+
+    assert_eq!(submit(Outcome::U64(50)), Ok(Verdict::TooHigh));
+    assert!(submit(Outcome::U64(51)).is_err()); // Not sent
+    assert_eq!(submit(Outcome::I64(-1)), Ok(Verdict::TooLow));
+    assert_eq!(submit(Outcome::U64(42)), Ok(Verdict::Right));
+    assert_eq!(journal.attempts(3, 2).len(), 3);
+
+    let first = requests.recv().unwrap();
+    assert_eq!(first.line, "POST /2025/day/3/answer HTTP/1.1");
+    assert_eq!(first.body, "level=2&answer=50");
+}
+
+#[test]
+fn submit_without_session() {
+    let (base_url, _requests) = stand_in(1, judge);
+    let submitter = HttpSubmitter::new(&base_url, "guess");
+    let mut journal = Journal::default();
+    let result = journal::submit(&mut journal, &submitter, (3, 1), &Outcome::U64(42), false);
+    assert!(result.is_err());
+    assert!(journal.attempts(3, 1).is_empty());
+}